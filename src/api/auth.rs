@@ -1,11 +1,11 @@
-use base64;
+use base64::{engine::general_purpose::STANDARD, Engine};
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE};
 use std::{env, fs};
 
 use crate::{
-    auth_completed,
+    app::{auth_completed, logout_completed},
     database::user::{save_user, User},
-    logout_completed, set_auth,
+    utils::config::get_config,
 };
 
 pub async fn authorize(code: &str) -> Result<(), Box<dyn std::error::Error>> {
@@ -13,7 +13,7 @@ pub async fn authorize(code: &str) -> Result<(), Box<dyn std::error::Error>> {
     let client_secret = env::var("OAUTH_CLIENT_SECRET")?;
     let redirect_uri = env::var("OAUTH_REDIRECT_URI")?;
 
-    let encoded = base64::encode(format!("{}:{}", client_id, client_secret));
+    let encoded = STANDARD.encode(format!("{}:{}", client_id, client_secret));
 
     let client = reqwest::Client::new();
 
@@ -60,7 +60,6 @@ pub async fn authorize(code: &str) -> Result<(), Box<dyn std::error::Error>> {
         };
 
         save_user(user);
-        set_auth(true);
         auth_completed();
     } else {
         println!("Failed to get token: {:?}", response.status());
@@ -70,10 +69,6 @@ pub async fn authorize(code: &str) -> Result<(), Box<dyn std::error::Error>> {
 }
 
 pub fn logout() {
-    match fs::remove_file("./ncli.db") {
-        Ok(_) => set_auth(false),
-        Err(_) => set_auth(false),
-    };
-
+    let _ = fs::remove_file(get_config().db_path);
     logout_completed();
 }
@@ -0,0 +1,286 @@
+use std::error::Error;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{api::api_error, database::user::get_access_token, utils::config::notion_version};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct RichText {
+    pub text: String,
+    pub bold: bool,
+    pub italic: bool,
+    pub strikethrough: bool,
+    pub underline: bool,
+    pub code: bool,
+    pub href: Option<String>,
+    #[serde(default)]
+    pub mention: Option<String>,
+    // none for notion's default colour
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Block {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub block_type: String,
+    pub text: Vec<RichText>,
+    pub checked: Option<bool>,
+    pub language: Option<String>,
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub cells: Vec<Vec<RichText>>,
+    pub children: Vec<Block>,
+}
+
+pub fn parse_rich_text(value: &Value) -> Vec<RichText> {
+    value
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .map(|item| RichText {
+                    text: item["plain_text"].as_str().unwrap_or("").to_string(),
+                    bold: item["annotations"]["bold"].as_bool().unwrap_or(false),
+                    italic: item["annotations"]["italic"].as_bool().unwrap_or(false),
+                    strikethrough: item["annotations"]["strikethrough"]
+                        .as_bool()
+                        .unwrap_or(false),
+                    underline: item["annotations"]["underline"].as_bool().unwrap_or(false),
+                    code: item["annotations"]["code"].as_bool().unwrap_or(false),
+                    href: item["href"].as_str().map(|href| href.to_string()),
+                    mention: item["mention"]["type"].as_str().map(|mention| mention.to_string()),
+                    color: item["annotations"]["color"]
+                        .as_str()
+                        .filter(|color| *color != "default")
+                        .map(|color| color.to_string()),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// plain text as rich text json, split into the 2000 character pieces notion accepts
+pub fn text_to_rich_text(text: &str) -> Value {
+    let chars: Vec<char> = text.chars().collect();
+    Value::Array(
+        chars
+            .chunks(2000)
+            .map(|chunk| serde_json::json!({ "type": "text", "text": { "content": chunk.iter().collect::<String>() } }))
+            .collect(),
+    )
+}
+
+fn parse_block(result: &Value) -> Block {
+    let block_type = result["type"].as_str().unwrap_or("unsupported").to_string();
+    let content = &result[&block_type];
+
+    let text = match block_type.as_str() {
+        "child_page" | "child_database" => vec![RichText {
+            text: content["title"].as_str().unwrap_or("Untitled").to_string(),
+            ..Default::default()
+        }],
+        "equation" => vec![RichText {
+            text: content["expression"].as_str().unwrap_or("").to_string(),
+            ..Default::default()
+        }],
+        // media blocks only carry a caption
+        "image" | "video" | "file" | "pdf" | "bookmark" | "embed" => {
+            parse_rich_text(&content["caption"])
+        }
+        _ => parse_rich_text(&content["rich_text"]),
+    };
+
+    // links live on the block itself, or under `file`/`external` for uploads
+    let url = content["url"]
+        .as_str()
+        .or_else(|| content["file"]["url"].as_str())
+        .or_else(|| content["external"]["url"].as_str())
+        .map(|url| url.to_string());
+
+    Block {
+        id: result["id"].as_str().unwrap_or("").to_string(),
+        block_type,
+        text,
+        checked: content["checked"].as_bool(),
+        language: content["language"].as_str().map(|language| language.to_string()),
+        icon: content["icon"]["emoji"].as_str().map(|icon| icon.to_string()),
+        url,
+        cells: content["cells"]
+            .as_array()
+            .map(|cells| cells.iter().map(parse_rich_text).collect())
+            .unwrap_or_default(),
+        children: Vec::new(),
+    }
+}
+
+// fetch every child of a block (or page), descending into nested blocks
+pub async fn get_block_children(block_id: &str) -> Result<Vec<Block>, Box<dyn Error>> {
+    let client = Client::new();
+    let mut blocks = Vec::<Block>::new();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let mut request = client
+            .get(format!(
+                "https://api.notion.com/v1/blocks/{}/children",
+                block_id
+            ))
+            .header("Authorization", format!("Bearer {}", get_access_token()))
+            .header("Notion-Version", notion_version())
+            .query(&[("page_size", "100")]);
+        if let Some(cursor) = &cursor {
+            request = request.query(&[("start_cursor", cursor)]);
+        }
+
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(api_error("get blocks", response).await);
+        }
+        let json_data = response.json::<Value>().await?;
+
+        if let Some(results) = json_data["results"].as_array() {
+            for result in results {
+                let mut block = parse_block(result);
+                let nested = result["has_children"].as_bool().unwrap_or(false)
+                    && block.block_type != "child_page"
+                    && block.block_type != "child_database";
+                if nested {
+                    block.children = Box::pin(get_block_children(&block.id)).await?;
+                }
+                blocks.push(block);
+            }
+        }
+
+        cursor = json_data["next_cursor"].as_str().map(|cursor| cursor.to_string());
+        if !json_data["has_more"].as_bool().unwrap_or(false) || cursor.is_none() {
+            break;
+        }
+    }
+
+    Ok(blocks)
+}
+
+// append blocks under a parent, at most 100 per request as the api allows, placing
+// them after the `after` block when given. nested children are sent level by level
+// since a request can only carry two levels. returns the ids of the top level blocks
+pub async fn append_block_children(
+    block_id: &str,
+    after: Option<&str>,
+    blocks: Vec<Value>,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let client = Client::new();
+    let mut ids = Vec::<String>::new();
+
+    for chunk in blocks.chunks(100) {
+        let mut chunk = chunk.to_vec();
+        let mut nested = Vec::<Vec<Value>>::new();
+        for block in chunk.iter_mut() {
+            let block_type = block["type"].as_str().unwrap_or("").to_string();
+            // table rows have to be created together with their table
+            let children = match block[&block_type].as_object_mut() {
+                Some(content) if block_type != "table" => content.remove("children"),
+                _ => None,
+            };
+            nested.push(
+                children
+                    .and_then(|children| children.as_array().cloned())
+                    .unwrap_or_default(),
+            );
+        }
+
+        let mut body = serde_json::json!({ "children": chunk });
+        // later chunks follow the last block of the one before
+        if let Some(after) = ids.last().map(|id| id.as_str()).or(after) {
+            body["after"] = Value::String(after.to_string());
+        }
+
+        let response = client
+            .patch(format!(
+                "https://api.notion.com/v1/blocks/{}/children",
+                block_id
+            ))
+            .header("Authorization", format!("Bearer {}", get_access_token()))
+            .header("Notion-Version", notion_version())
+            .json(&body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error("append blocks", response).await);
+        }
+        let json_data = response.json::<Value>().await?;
+
+        // the response should hold just the new blocks, but older api versions list
+        // the parent's children instead, so pick ours out by position in that case
+        let results = json_data["results"].as_array().cloned().unwrap_or_default();
+        let created: Vec<Value> = match body["after"].as_str() {
+            _ if results.len() == chunk.len() => results,
+            Some(after) => results
+                .iter()
+                .skip_while(|block| block["id"] != after)
+                .skip(1)
+                .take(chunk.len())
+                .cloned()
+                .collect(),
+            None => results.iter().rev().take(chunk.len()).rev().cloned().collect(),
+        };
+        if created.len() != chunk.len() {
+            return Err("Failed to append blocks: the new blocks were not in the response".into());
+        }
+
+        for (block, children) in created.iter().zip(nested) {
+            let id = block["id"].as_str().unwrap_or("");
+            if !children.is_empty() {
+                Box::pin(append_block_children(id, None, children)).await?;
+            }
+            ids.push(id.to_string());
+        }
+    }
+
+    Ok(ids)
+}
+
+// replace a block's content, its children are left alone
+pub async fn update_block(block_id: &str, block: &Value) -> Result<(), Box<dyn Error>> {
+    let block_type = block["type"].as_str().unwrap_or("");
+    let mut content = block[block_type].clone();
+    if let Some(content) = content.as_object_mut() {
+        content.remove("children");
+    }
+
+    let client = Client::new();
+    let response = client
+        .patch(format!("https://api.notion.com/v1/blocks/{}", block_id))
+        .header("Authorization", format!("Bearer {}", get_access_token()))
+        .header("Notion-Version", notion_version())
+        .json(&serde_json::json!({ block_type: content }))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(api_error("update block", response).await);
+    }
+    Ok(())
+}
+
+// deleting moves the block to the trash along with its children
+pub async fn delete_block(block_id: &str) -> Result<(), Box<dyn Error>> {
+    let client = Client::new();
+    let response = client
+        .delete(format!("https://api.notion.com/v1/blocks/{}", block_id))
+        .header("Authorization", format!("Bearer {}", get_access_token()))
+        .header("Notion-Version", notion_version())
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(api_error("delete block", response).await);
+    }
+    Ok(())
+}
@@ -0,0 +1,635 @@
+use std::error::Error;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    api::{
+        api_error,
+        blocks::text_to_rich_text,
+        search::{is_notion_id, Object},
+        users::Person,
+    },
+    database::user::get_access_token,
+    utils::config::notion_version,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Column {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub property_type: String,
+    // option names for select, multi_select and status columns
+    #[serde(default)]
+    pub options: Vec<String>,
+    // status options in notion's complete group
+    #[serde(default)]
+    pub done: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Row {
+    pub id: String,
+    pub properties: Value,
+}
+
+// fetch the database schema, title column first
+pub async fn get_columns(database_id: &str) -> Result<Vec<Column>, Box<dyn Error>> {
+    let client = Client::new();
+    let response = client
+        .get(format!("https://api.notion.com/v1/databases/{}", database_id))
+        .header("Authorization", format!("Bearer {}", get_access_token()))
+        .header("Notion-Version", notion_version())
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(api_error("get database", response).await);
+    }
+    let json_data = response.json::<Value>().await?;
+
+    let mut columns: Vec<Column> = json_data["properties"]
+        .as_object()
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(name, property)| {
+                    let property_type = property["type"].as_str().unwrap_or("").to_string();
+                    let options = property[&property_type]["options"].as_array().cloned().unwrap_or_default();
+                    let done = property["status"]["groups"]
+                        .as_array()
+                        .and_then(|groups| groups.iter().find(|group| group["name"] == "Complete"))
+                        .and_then(|group| group["option_ids"].as_array())
+                        .map(|ids| {
+                            options
+                                .iter()
+                                .filter(|option| ids.contains(&option["id"]))
+                                .filter_map(|option| option["name"].as_str())
+                                .map(|name| name.to_string())
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    Column {
+                        name: name.clone(),
+                        options: options
+                            .iter()
+                            .filter_map(|option| option["name"].as_str())
+                            .map(|name| name.to_string())
+                            .collect(),
+                        done,
+                        property_type,
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    columns.sort_by_key(|column| column.property_type != "title");
+
+    Ok(columns)
+}
+
+// query every row of a database following `next_cursor`
+pub async fn query_database(database_id: &str) -> Result<Vec<Row>, Box<dyn Error>> {
+    query_database_filtered(database_id, Value::Null, Value::Null).await
+}
+
+// rows matching a notion filter object in the order of the sorts array, either can be null
+pub async fn query_database_filtered(
+    database_id: &str,
+    filter: Value,
+    sorts: Value,
+) -> Result<Vec<Row>, Box<dyn Error>> {
+    let client = Client::new();
+    let mut rows = Vec::<Row>::new();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let mut body = serde_json::json!({ "page_size": 100 });
+        if !filter.is_null() {
+            body["filter"] = filter.clone();
+        }
+        if !sorts.is_null() {
+            body["sorts"] = sorts.clone();
+        }
+        if let Some(cursor) = &cursor {
+            body["start_cursor"] = Value::String(cursor.clone());
+        }
+
+        let response = client
+            .post(format!(
+                "https://api.notion.com/v1/databases/{}/query",
+                database_id
+            ))
+            .header("Authorization", format!("Bearer {}", get_access_token()))
+            .header("Notion-Version", notion_version())
+            .json(&body)
+            .send()
+            .await?;
+
+        // notion explains filters it rejects in the message
+        if !response.status().is_success() {
+            return Err(api_error("query database", response).await);
+        }
+        let json_data = response.json::<Value>().await?;
+
+        if let Some(results) = json_data["results"].as_array() {
+            for result in results {
+                rows.push(Row {
+                    id: result["id"].as_str().unwrap_or("").to_string(),
+                    properties: result["properties"].clone(),
+                });
+            }
+        }
+
+        cursor = json_data["next_cursor"].as_str().map(|cursor| cursor.to_string());
+        if !json_data["has_more"].as_bool().unwrap_or(false) || cursor.is_none() {
+            break;
+        }
+    }
+
+    Ok(rows)
+}
+
+fn plain_text(value: &Value) -> String {
+    value
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item["plain_text"].as_str())
+                .collect::<String>()
+        })
+        .unwrap_or_default()
+}
+
+fn date_text(value: &Value) -> String {
+    match (value["start"].as_str(), value["end"].as_str()) {
+        (Some(start), Some(end)) => format!("{} → {}", start, end),
+        (Some(start), None) => start.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn number_text(value: &Value) -> String {
+    match value.as_f64() {
+        Some(number) if number.fract() == 0.0 => format!("{}", number as i64),
+        Some(number) => number.to_string(),
+        None => String::new(),
+    }
+}
+
+// flatten a property value into a single display string
+pub fn property_text(property: &Value) -> String {
+    let property_type = property["type"].as_str().unwrap_or("");
+    let value = &property[property_type];
+
+    match property_type {
+        "title" | "rich_text" => plain_text(value),
+        "select" | "status" => value["name"].as_str().unwrap_or("").to_string(),
+        "multi_select" => value
+            .as_array()
+            .map(|options| {
+                options
+                    .iter()
+                    .filter_map(|option| option["name"].as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            })
+            .unwrap_or_default(),
+        "date" => date_text(value),
+        "checkbox" => if value.as_bool().unwrap_or(false) { "[x]" } else { "[ ]" }.to_string(),
+        "number" => number_text(value),
+        "people" => value
+            .as_array()
+            .map(|people| {
+                people
+                    .iter()
+                    .filter_map(|person| person["name"].as_str().or(person["id"].as_str()))
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            })
+            .unwrap_or_default(),
+        "relation" => value
+            .as_array()
+            .map(|pages| {
+                pages
+                    .iter()
+                    .filter_map(|page| page["id"].as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            })
+            .unwrap_or_default(),
+        "formula" => {
+            let formula_type = value["type"].as_str().unwrap_or("");
+            match formula_type {
+                "string" => value["string"].as_str().unwrap_or("").to_string(),
+                "number" => number_text(&value["number"]),
+                "boolean" => value["boolean"].as_bool().map(|b| b.to_string()).unwrap_or_default(),
+                "date" => date_text(&value["date"]),
+                _ => String::new(),
+            }
+        }
+        "url" | "email" | "phone_number" | "created_time" | "last_edited_time" => {
+            value.as_str().unwrap_or("").to_string()
+        }
+        "created_by" | "last_edited_by" => value["name"].as_str().unwrap_or("").to_string(),
+        "unique_id" => match (value["prefix"].as_str(), value["number"].as_i64()) {
+            (Some(prefix), Some(number)) => format!("{}-{}", prefix, number),
+            (None, Some(number)) => number.to_string(),
+            _ => String::new(),
+        },
+        "files" => value
+            .as_array()
+            .map(|files| {
+                files
+                    .iter()
+                    .filter_map(|file| file["name"].as_str())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            })
+            .unwrap_or_default(),
+        "rollup" => match value["type"].as_str().unwrap_or("") {
+            "number" => number_text(&value["number"]),
+            "date" => date_text(&value["date"]),
+            "array" => value["array"]
+                .as_array()
+                .map(|items| items.iter().map(property_text).collect::<Vec<String>>().join(", "))
+                .unwrap_or_default(),
+            _ => String::new(),
+        },
+        _ => String::new(),
+    }
+}
+
+fn names(value: &Value, key: &str) -> Value {
+    Value::Array(
+        value
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item[key].as_str())
+                    .map(|name| Value::String(name.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    )
+}
+
+// iso 8601 writes a range as start/end
+fn date_value(value: &Value) -> Value {
+    match (value["start"].as_str(), value["end"].as_str()) {
+        (Some(start), Some(end)) => Value::String(format!("{}/{}", start, end)),
+        (Some(start), None) => Value::String(start.to_string()),
+        _ => Value::Null,
+    }
+}
+
+// a property as a plain json value for exports, typed where the type is obvious
+pub fn property_value(property: &Value) -> Value {
+    let property_type = property["type"].as_str().unwrap_or("");
+    let value = &property[property_type];
+
+    match property_type {
+        "title" | "rich_text" => Value::String(plain_text(value)),
+        "select" | "status" => value["name"].as_str().map_or(Value::Null, |name| Value::String(name.to_string())),
+        "multi_select" => names(value, "name"),
+        "date" => date_value(value),
+        "checkbox" => Value::Bool(value.as_bool().unwrap_or(false)),
+        "number" => value.clone(),
+        "people" => Value::Array(
+            value
+                .as_array()
+                .map(|people| {
+                    people
+                        .iter()
+                        .filter_map(|person| {
+                            person["person"]["email"]
+                                .as_str()
+                                .or(person["name"].as_str())
+                                .or(person["id"].as_str())
+                        })
+                        .map(|person| Value::String(person.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        ),
+        "relation" => names(value, "id"),
+        "files" => names(value, "name"),
+        "formula" => match value["type"].as_str().unwrap_or("") {
+            "date" => date_value(&value["date"]),
+            formula_type => value[formula_type].clone(),
+        },
+        "rollup" => match value["type"].as_str().unwrap_or("") {
+            "date" => date_value(&value["date"]),
+            "array" => Value::Array(
+                value["array"]
+                    .as_array()
+                    .map(|items| items.iter().map(property_value).collect())
+                    .unwrap_or_default(),
+            ),
+            rollup_type => value[rollup_type].clone(),
+        },
+        "created_by" | "last_edited_by" => value["person"]["email"]
+            .as_str()
+            .or(value["name"].as_str())
+            .or(value["id"].as_str())
+            .map_or(Value::Null, |person| Value::String(person.to_string())),
+        "unique_id" => Value::String(property_text(property)),
+        _ => value.clone(),
+    }
+}
+
+// properties notion computes itself and refuses to have written
+pub fn is_writable(property_type: &str) -> bool {
+    matches!(
+        property_type,
+        "title"
+            | "rich_text"
+            | "number"
+            | "select"
+            | "multi_select"
+            | "status"
+            | "date"
+            | "checkbox"
+            | "url"
+            | "email"
+            | "phone_number"
+            | "people"
+            | "relation"
+            | "files"
+    )
+}
+
+fn valid_date(date: &str) -> bool {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok()
+        || DateTime::parse_from_rfc3339(date).is_ok()
+        || NaiveDateTime::parse_from_str(date, "%Y-%m-%dT%H:%M:%S").is_ok()
+        || NaiveDateTime::parse_from_str(date, "%Y-%m-%dT%H:%M").is_ok()
+}
+
+fn split_list(raw: &str) -> Vec<&str> {
+    raw.split(',').map(|item| item.trim()).filter(|item| !item.is_empty()).collect()
+}
+
+// turn a cell of text into the json notion expects for a property of that column,
+// the inverse of property_value. empty cells give None and are left unset
+pub fn coerce_property(column: &Column, raw: &str, people: &[Person]) -> Result<Option<Value>, String> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Ok(None);
+    }
+
+    let value = match column.property_type.as_str() {
+        "title" | "rich_text" => text_to_rich_text(raw),
+        "number" => {
+            let number: f64 = raw
+                .replace(',', "")
+                .parse()
+                .map_err(|_| format!("\"{}\" is not a number", raw))?;
+            json!(number)
+        }
+        "checkbox" => match raw.to_lowercase().as_str() {
+            "true" | "yes" | "y" | "1" | "x" | "[x]" => json!(true),
+            "false" | "no" | "n" | "0" | "[ ]" => json!(false),
+            _ => return Err(format!("\"{}\" is not true or false", raw)),
+        },
+        // a select holds one option, commas and all
+        "select" | "status" => return coerce_items(column, &[raw], people),
+        "multi_select" | "people" | "relation" | "files" => return coerce_items(column, &split_list(raw), people),
+        "date" => {
+            // ranges come as start/end, the way export-db writes them
+            let (start, end) = match raw.split_once('/').or_else(|| raw.split_once(" → ")) {
+                Some((start, end)) => (start.trim(), Some(end.trim())),
+                None => (raw, None),
+            };
+            if let Some(bad) = std::iter::once(start).chain(end).find(|date| !valid_date(date)) {
+                return Err(format!("\"{}\" is not an ISO 8601 date", bad));
+            }
+            json!({ "start": start, "end": end })
+        }
+        "url" | "phone_number" => json!(raw),
+        "email" => {
+            if !raw.contains('@') {
+                return Err(format!("\"{}\" is not an email address", raw));
+            }
+            json!(raw)
+        }
+        other => return Err(format!("{} properties can't be written", other)),
+    };
+
+    Ok(Some(json!({ column.property_type.clone(): value })))
+}
+
+// like coerce_property for the properties that hold options, people, pages or files,
+// from items already told apart so a name with a comma in it stays whole
+pub fn coerce_items(column: &Column, items: &[&str], people: &[Person]) -> Result<Option<Value>, String> {
+    let items: Vec<&str> = items.iter().map(|item| item.trim()).filter(|item| !item.is_empty()).collect();
+    if items.is_empty() {
+        return Ok(None);
+    }
+
+    let value = match column.property_type.as_str() {
+        "select" | "status" if items.len() > 1 => {
+            return Err(format!("{} takes one option, not {}", column.name, items.len()))
+        }
+        "select" => json!({ "name": items[0] }),
+        "status" => {
+            let option = column
+                .options
+                .iter()
+                .find(|option| option.eq_ignore_ascii_case(items[0]))
+                .ok_or_else(|| format!("\"{}\" is not one of {}", items[0], column.options.join(", ")))?;
+            json!({ "name": option })
+        }
+        "multi_select" => json!(items.iter().map(|name| json!({ "name": name })).collect::<Vec<Value>>()),
+        "people" => {
+            let ids = items
+                .iter()
+                .map(|person| {
+                    if is_notion_id(person) {
+                        return Ok(person.to_string());
+                    }
+                    people
+                        .iter()
+                        .find(|known| {
+                            known.email.as_deref().is_some_and(|email| email.eq_ignore_ascii_case(person))
+                                || known.name.eq_ignore_ascii_case(person)
+                        })
+                        .map(|known| known.id.clone())
+                        .ok_or_else(|| format!("no workspace member \"{}\"", person))
+                })
+                .collect::<Result<Vec<String>, String>>()?;
+            json!(ids.iter().map(|id| json!({ "id": id })).collect::<Vec<Value>>())
+        }
+        "relation" => {
+            if let Some(bad) = items.iter().find(|id| !is_notion_id(id)) {
+                return Err(format!("\"{}\" is not a page id", bad));
+            }
+            json!(items.iter().map(|id| json!({ "id": id })).collect::<Vec<Value>>())
+        }
+        "files" => json!(items
+            .iter()
+            .map(|url| json!({ "name": url, "type": "external", "external": { "url": url } }))
+            .collect::<Vec<Value>>()),
+        other => return Err(format!("{} properties can't be written", other)),
+    };
+
+    Ok(Some(json!({ column.property_type.clone(): value })))
+}
+
+// what a property is set to when it is cleared
+pub fn empty_property(column: &Column) -> Value {
+    let value = match column.property_type.as_str() {
+        "title" | "rich_text" | "multi_select" | "people" | "relation" | "files" => json!([]),
+        _ => Value::Null,
+    };
+    json!({ column.property_type.clone(): value })
+}
+
+// patch some properties of a row, returning all of them as notion now has them
+pub async fn update_properties(page_id: &str, properties: Value) -> Result<Value, Box<dyn Error>> {
+    let client = Client::new();
+    let response = client
+        .patch(format!("https://api.notion.com/v1/pages/{}", page_id))
+        .header("Authorization", format!("Bearer {}", get_access_token()))
+        .header("Notion-Version", notion_version())
+        .json(&json!({ "properties": properties }))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(api_error("update row", response).await);
+    }
+    let json_data = response.json::<Value>().await?;
+
+    Ok(json_data["properties"].clone())
+}
+
+// add a row to a database, returning the new page's id
+pub async fn create_row(database_id: &str, properties: Value) -> Result<String, Box<dyn Error>> {
+    let client = Client::new();
+    let response = client
+        .post("https://api.notion.com/v1/pages")
+        .header("Authorization", format!("Bearer {}", get_access_token()))
+        .header("Notion-Version", notion_version())
+        .json(&json!({
+            "parent": { "database_id": database_id },
+            "properties": properties,
+        }))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(api_error("create row", response).await);
+    }
+    let json_data = response.json::<Value>().await?;
+
+    Ok(json_data["id"].as_str().unwrap_or("").to_string())
+}
+
+// add a row with only its title filled in
+pub async fn create_titled_row(database_id: &str, title: &str) -> Result<Object, Box<dyn Error>> {
+    let columns = get_columns(database_id).await?;
+    let title_column = columns
+        .iter()
+        .find(|column| column.property_type == "title")
+        .ok_or("database has no title property")?;
+
+    let id = create_row(
+        database_id,
+        json!({ title_column.name.clone(): { "title": text_to_rich_text(title) } }),
+    )
+    .await?;
+
+    Ok(Object {
+        id,
+        parent_id: Some(database_id.to_string()),
+        title: title.to_string(),
+        object_type: "page".to_string(),
+        children: Vec::new(),
+        last_edited_time: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(property_type: &str, options: &[&str]) -> Column {
+        Column {
+            name: "Column".to_string(),
+            property_type: property_type.to_string(),
+            options: options.iter().map(|option| option.to_string()).collect(),
+            done: Vec::new(),
+        }
+    }
+
+    fn coerce(property_type: &str, raw: &str) -> Result<Option<Value>, String> {
+        coerce_property(&column(property_type, &["Todo", "Done"]), raw, &[])
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(coerce("number", "1,234.5"), Ok(Some(json!({ "number": 1234.5 }))));
+        assert_eq!(coerce("number", " "), Ok(None));
+        assert!(coerce("number", "twelve").is_err());
+    }
+
+    #[test]
+    fn checkboxes() {
+        assert_eq!(coerce("checkbox", "[x]"), Ok(Some(json!({ "checkbox": true }))));
+        assert_eq!(coerce("checkbox", "No"), Ok(Some(json!({ "checkbox": false }))));
+        assert!(coerce("checkbox", "maybe").is_err());
+    }
+
+    #[test]
+    fn dates() {
+        assert_eq!(
+            coerce("date", "2024-05-01/2024-05-03"),
+            Ok(Some(json!({ "date": { "start": "2024-05-01", "end": "2024-05-03" } })))
+        );
+        assert_eq!(
+            coerce("date", "2024-05-01 → 2024-05-03"),
+            Ok(Some(json!({ "date": { "start": "2024-05-01", "end": "2024-05-03" } })))
+        );
+        assert!(coerce("date", "2024-13-01").is_err());
+        assert!(coerce("date", "2024-05-01/soon").is_err());
+    }
+
+    #[test]
+    fn selects() {
+        // a select cell is one option even with a comma in it
+        assert_eq!(coerce("select", "Q1, Q2"), Ok(Some(json!({ "select": { "name": "Q1, Q2" } }))));
+        assert_eq!(coerce("status", "done"), Ok(Some(json!({ "status": { "name": "Done" } }))));
+        assert!(coerce("status", "Blocked").is_err());
+        assert_eq!(
+            coerce("multi_select", "a, b"),
+            Ok(Some(json!({ "multi_select": [{ "name": "a" }, { "name": "b" }] })))
+        );
+    }
+
+    #[test]
+    fn picked_items() {
+        let tags = column("multi_select", &[]);
+        assert_eq!(
+            coerce_items(&tags, &["Q1, Q2", "Q3"], &[]),
+            Ok(Some(json!({ "multi_select": [{ "name": "Q1, Q2" }, { "name": "Q3" }] })))
+        );
+        assert_eq!(coerce_items(&tags, &[], &[]), Ok(None));
+        assert!(coerce_items(&column("select", &[]), &["a", "b"], &[]).is_err());
+
+        let people = [Person {
+            id: "user-1".to_string(),
+            name: "Sam".to_string(),
+            email: Some("sam@example.com".to_string()),
+        }];
+        assert_eq!(
+            coerce_items(&column("people", &[]), &["SAM@example.com"], &people),
+            Ok(Some(json!({ "people": [{ "id": "user-1" }] })))
+        );
+        assert!(coerce_items(&column("people", &[]), &["nobody"], &people).is_err());
+    }
+}
@@ -0,0 +1,132 @@
+use std::{error::Error, fs};
+
+use chrono::{
+    format::{Item, StrftimeItems},
+    NaiveDate,
+};
+use serde_json::{json, Value};
+
+use crate::{
+    api::{
+        blocks::{append_block_children, get_block_children, text_to_rich_text},
+        databases::{create_row, get_columns, property_text, query_database_filtered},
+        pages::create_page,
+        search::Object,
+        templates::fill_template,
+    },
+    database::{defaults::get_default, templates::get_template},
+    utils::config::{config_path, get_config},
+};
+
+pub fn valid_title_format(format: &str) -> bool {
+    !format.trim().is_empty() && !StrftimeItems::new(format).any(|item| item == Item::Error)
+}
+
+pub fn entry_title(date: NaiveDate) -> String {
+    let format = get_config().journal_title;
+    if valid_title_format(&format) {
+        date.format(&format).to_string()
+    } else {
+        date.format("%Y-%m-%d").to_string()
+    }
+}
+
+pub fn journal() -> Result<Object, Box<dyn Error>> {
+    get_default("journal")?
+        .ok_or_else(|| "no journal set, run notion_cli journal --set <page|database> first".into())
+}
+
+// an entry under a page is a child page with the entry title. in a database it is the
+// row whose first date property is that day, or whose title matches without one
+async fn find_entry(journal: &Object, date: NaiveDate, title: &str) -> Result<Option<Object>, Box<dyn Error>> {
+    let entry = |id: String, title: String| Object {
+        id,
+        parent_id: Some(journal.id.clone()),
+        title,
+        object_type: "page".to_string(),
+        children: Vec::new(),
+        last_edited_time: None,
+    };
+
+    if journal.object_type != "database" {
+        let blocks = get_block_children(&journal.id).await?;
+        return Ok(blocks
+            .into_iter()
+            .find(|block| {
+                block.block_type == "child_page"
+                    && block.text.iter().map(|text| text.text.as_str()).collect::<String>() == title
+            })
+            .map(|block| entry(block.id, title.to_string())));
+    }
+
+    let columns = get_columns(&journal.id).await?;
+    let title_column = columns
+        .iter()
+        .find(|column| column.property_type == "title")
+        .ok_or("database has no title property")?;
+    let filter = match columns.iter().find(|column| column.property_type == "date") {
+        Some(column) => json!({ "property": column.name, "date": { "equals": date.to_string() } }),
+        None => json!({ "property": title_column.name, "title": { "equals": title } }),
+    };
+
+    let rows = query_database_filtered(&journal.id, filter, Value::Null).await?;
+    Ok(rows.into_iter().next().map(|row| {
+        let title = property_text(&row.properties[&title_column.name]);
+        entry(row.id, title)
+    }))
+}
+
+// the stored "journal" template, or journal.md next to the config file
+fn template_blocks(date: NaiveDate, title: &str) -> Vec<Value> {
+    let body = match get_template("journal") {
+        Ok(Some(template)) => template.body,
+        _ => match fs::read_to_string(config_path().with_file_name("journal.md")) {
+            Ok(body) => body,
+            Err(_) => return Vec::new(),
+        },
+    };
+    fill_template(&body, date, title)
+}
+
+async fn create_entry(journal: &Object, date: NaiveDate, title: &str) -> Result<Object, Box<dyn Error>> {
+    let page = if journal.object_type == "database" {
+        let columns = get_columns(&journal.id).await?;
+        let mut properties = json!({});
+        if let Some(column) = columns.iter().find(|column| column.property_type == "title") {
+            properties[&column.name] = json!({ "title": text_to_rich_text(title) });
+        }
+        // the same date property find_entry looks at
+        if let Some(column) = columns.iter().find(|column| column.property_type == "date") {
+            properties[&column.name] = json!({ "date": { "start": date.to_string() } });
+        }
+
+        Object {
+            id: create_row(&journal.id, properties).await?,
+            parent_id: Some(journal.id.clone()),
+            title: title.to_string(),
+            object_type: "page".to_string(),
+            children: Vec::new(),
+            last_edited_time: None,
+        }
+    } else {
+        create_page(&journal.id, title).await?
+    };
+
+    let blocks = template_blocks(date, title);
+    if !blocks.is_empty() {
+        append_block_children(&page.id, None, blocks).await?;
+    }
+    Ok(page)
+}
+
+// the journal entry for a day, created from the template when missing.
+// also returns whether it had to be created
+pub async fn journal_entry(date: NaiveDate) -> Result<(Object, bool), Box<dyn Error>> {
+    let journal = journal()?;
+    let title = entry_title(date);
+
+    match find_entry(&journal, date, &title).await? {
+        Some(entry) => Ok((entry, false)),
+        None => Ok((create_entry(&journal, date, &title).await?, true)),
+    }
+}
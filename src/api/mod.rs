@@ -1,9 +1,17 @@
 pub mod pages;
 pub mod auth;
+pub mod blocks;
+pub mod databases;
+pub mod journal;
 pub mod search;
+pub mod templates;
+pub mod todos;
+pub mod users;
 
-use std::sync::Once;
+use std::{error::Error, sync::Once};
 use notion::NotionApi;
+use reqwest::Response;
+use serde_json::Value;
 
 use crate::database::user::get_user;
 
@@ -23,6 +31,16 @@ pub fn initialize_notion() {
     });
 }
 
+#[allow(dead_code)]
 pub fn get_notion() -> &'static NotionApi {
-    unsafe { NOTION.as_ref().expect("Database not initialized") }
-}
\ No newline at end of file
+    unsafe { (*std::ptr::addr_of!(NOTION)).as_ref().expect("Database not initialized") }
+}
+
+// the error for a failed request, with the explanation notion sends in the body
+pub async fn api_error(action: &str, response: Response) -> Box<dyn Error> {
+    let status = response.status();
+    let body = response.json::<Value>().await.unwrap_or_default();
+    format!("Failed to {}: {} {}", action, status, body["message"].as_str().unwrap_or(""))
+        .trim_end()
+        .into()
+}
@@ -1,35 +1,85 @@
-use std::fs;
+use std::error::Error;
 
-use crate::database::user::get_access_token;
+use crate::{
+    api::{api_error, search::Object},
+    database::user::get_access_token,
+    utils::config::notion_version,
+};
 
 use reqwest::Client;
+use serde_json::Value;
 
+// create an empty page under another page, content is appended separately
+pub async fn create_page(parent_id: &str, title: &str) -> Result<Object, Box<dyn Error>> {
+    let client = Client::new();
+
+    let response = client
+        .post("https://api.notion.com/v1/pages")
+        .header("Authorization", format!("Bearer {}", get_access_token()))
+        .header("Notion-Version", notion_version())
+        .json(&serde_json::json!({
+            "parent": { "page_id": parent_id },
+            "properties": {
+                "title": { "title": [{ "type": "text", "text": { "content": title } }] }
+            },
+        }))
+        .send()
+        .await?;
 
-pub async fn get_pages() -> Result<serde_json::Value, Box<dyn std::error::Error>> {
-    let access_token = get_access_token();
-    if access_token.is_empty() {
-        // logout();
+    if !response.status().is_success() {
+        return Err(api_error("create page", response).await);
     }
+    let json_data = response.json::<Value>().await?;
+
+    Ok(Object {
+        id: json_data["id"].as_str().unwrap_or("").to_string(),
+        parent_id: Some(parent_id.to_string()),
+        title: title.to_string(),
+        object_type: "page".to_string(),
+        children: Vec::new(),
+        last_edited_time: json_data["last_edited_time"].as_str().map(|time| time.to_string()),
+    })
+}
 
+// move a page or database to the trash, or bring it back
+pub async fn set_archived(object: &Object, archived: bool) -> Result<(), Box<dyn Error>> {
+    let endpoint = if object.object_type == "database" { "databases" } else { "pages" };
     let client = Client::new();
 
     let response = client
-        .post("https://api.notion.com/v1/search")
-        .header("Authorization", format!("Bearer {}", access_token))
-        .header("Notion-Version", "2022-06-28")
-        .json(&serde_json::json!({  "query": "" }))
+        .patch(format!("https://api.notion.com/v1/{}/{}", endpoint, object.id))
+        .header("Authorization", format!("Bearer {}", get_access_token()))
+        .header("Notion-Version", notion_version())
+        .json(&serde_json::json!({ "archived": archived }))
         .send()
         .await?;
 
-    let mut json_data = serde_json::json!({});
-    if response.status().is_success() {
-        json_data = response.json::<serde_json::Value>().await?;
+    if !response.status().is_success() {
+        let action = if archived { "archive" } else { "restore" };
+        return Err(api_error(&format!("{} {}", action, object.object_type), response).await);
     }
-
-    fs::write("output.txt", json_data.to_string()).unwrap();
-
-    return Ok(json_data);
+    Ok(())
 }
 
+// give a page a new parent page, or make it a row of a database
+pub async fn move_page(page_id: &str, parent: &Object) -> Result<(), Box<dyn Error>> {
+    let parent_json = if parent.object_type == "database" {
+        serde_json::json!({ "type": "database_id", "database_id": parent.id })
+    } else {
+        serde_json::json!({ "type": "page_id", "page_id": parent.id })
+    };
+    let client = Client::new();
 
+    let response = client
+        .post(format!("https://api.notion.com/v1/pages/{}/move", page_id))
+        .header("Authorization", format!("Bearer {}", get_access_token()))
+        .header("Notion-Version", notion_version())
+        .json(&serde_json::json!({ "parent": parent_json }))
+        .send()
+        .await?;
 
+    if !response.status().is_success() {
+        return Err(api_error("move page", response).await);
+    }
+    Ok(())
+}
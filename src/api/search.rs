@@ -1,9 +1,18 @@
 use std::{collections::HashMap, error::Error};
 
-use serde::{Deserialize, Serialize};use reqwest::Client;
+use indexmap::IndexMap;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::database::user::get_access_token;
+use crate::{
+    api::api_error,
+    database::user::get_access_token,
+    utils::{
+        config::{get_config, notion_version},
+        search::match_search,
+    },
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Object {
@@ -13,48 +22,61 @@ pub struct Object {
     #[serde(rename = "type")]
     pub object_type: String,
     pub children: Vec<Object>,
+    #[serde(default)]
+    pub last_edited_time: Option<String>,
 }
 
-async fn get_parent_id(client: &Client, parent: Value) -> Result<Option<String>, Box<dyn Error>> {
+// the page a block sits in, keyed by block id. one per listing, so objects under the
+// same blocks don't look them up again
+type BlockPages = HashMap<String, Option<String>>;
+
+async fn get_parent_id(
+    client: &Client,
+    parent: &Value,
+    block_pages: &mut BlockPages,
+) -> Result<Option<String>, Box<dyn Error>> {
     if parent["type"] == "workspace" {
-        return Ok(None);
-    } else {
-        if parent["type"] == "page_id" {
-            return Ok(Some(parent["page_id"].as_str().unwrap().to_string()));
-        } else if parent["type"] == "database_id" {
-            return Ok(Some(parent["database_id"].as_str().unwrap().to_string()));
-        } else if parent["type"] == "block_id" {
-            let mut current_block_id = parent["block_id"].as_str().unwrap().to_string();
-            loop {
-                let response = client
-                    .get(format!(
-                        "https://api.notion.com/v1/blocks/{}",
-                        current_block_id
-                    ))
-                    .header("Authorization", format!("Bearer {}", get_access_token()))
-                    .header("Notion-Version", "2022-06-28")
-                    .send()
-                    .await?;
-
-                let mut result = serde_json::json!({});
-                if response.status().is_success() {
-                    result = response.json::<serde_json::Value>().await?;
-                }
+        Ok(None)
+    } else if parent["type"] == "page_id" {
+        Ok(parent["page_id"].as_str().map(|id| id.to_string()))
+    } else if parent["type"] == "database_id" {
+        Ok(parent["database_id"].as_str().map(|id| id.to_string()))
+    } else if parent["type"] == "block_id" {
+        let mut current_block_id = parent["block_id"].as_str().unwrap_or_default().to_string();
+        let mut walked = Vec::<String>::new();
+        // a block that can't be read leaves the object at the top level
+        let page_id = loop {
+            if let Some(page_id) = block_pages.get(&current_block_id) {
+                break page_id.clone();
+            }
+            walked.push(current_block_id.clone());
 
-                let parent_type = result["parent"]["type"].as_str().unwrap().to_string();
-                if parent_type == "page_id".to_string() {
-                    return Ok(Some(
-                        result["parent"]["page_id"].as_str().unwrap().to_string(),
-                    ));
-                } else if parent_type == "block_id".to_string() {
-                    current_block_id = result["parent"]["block_id"].as_str().unwrap().to_string();
-                } else {
-                    return Err("Unexpected parent type".into());
-                }
+            let response = client
+                .get(format!(
+                    "https://api.notion.com/v1/blocks/{}",
+                    current_block_id
+                ))
+                .header("Authorization", format!("Bearer {}", get_access_token()))
+                .header("Notion-Version", notion_version())
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                break None;
             }
-        } else {
-            return Err("Invalid parent type".into());
+            let result = response.json::<Value>().await?;
+
+            match (result["parent"]["type"].as_str(), result["parent"]["block_id"].as_str()) {
+                (Some("page_id"), _) => break result["parent"]["page_id"].as_str().map(|id| id.to_string()),
+                (Some("block_id"), Some(block_id)) => current_block_id = block_id.to_string(),
+                _ => break None,
+            }
+        };
+        for block_id in walked {
+            block_pages.insert(block_id, page_id.clone());
         }
+        Ok(page_id)
+    } else {
+        Err("Invalid parent type".into())
     }
 }
 
@@ -100,55 +122,292 @@ fn get_title(object_type: String, result: &Value) -> Result<String, Box<dyn Erro
         .to_string())
 }
 
-pub async fn search_api(query: Option<String>) -> Result<HashMap<String, Object>, reqwest::Error> {
-    let search_query = query.unwrap_or("".to_string());
-    let mut objects = HashMap::<String, Object>::new();
+impl Object {
+    pub fn url(&self) -> String {
+        format!("https://www.notion.so/{}", self.id.replace('-', ""))
+    }
+}
 
-    let client = Client::new();
-    let response = client
-        .post("https://api.notion.com/v1/search")
-        .header("Authorization", format!("Bearer {}", get_access_token()))
-        .header("Notion-Version", "2022-06-28")
-        .json(
-            &serde_json::json!({  "query": search_query, "page_size": 20, "sort":{
-              "direction":"ascending",
-              "timestamp":"last_edited_time"
-            }}),
-        )
-        .send()
-        .await?;
-
-    
-    let mut json_data = serde_json::json!({});
-    if response.status().is_success() {
-        json_data = response.json::<serde_json::Value>().await?;
-    }
-
-    if let Some(results) = json_data["results"].as_array() {
-        for result in results {
-            let id = result["id"].as_str().unwrap().to_string();
-            let parent_id = match get_parent_id(&client, result["parent"].clone()).await {
-                Ok(parent_id) => parent_id,
-                Err(_) => None,
-            };
-
-            let object_type = result["object"].as_str().unwrap().to_string();
-            let title = match get_title(object_type.clone(), result) {
-                Ok(title) => title,
-                Err(_) => "Untitled".to_string(),
-            };
-
-            let object = Object {
-                id: id.clone(),
-                parent_id,
-                title: title.clone(),
-                object_type,
-                children: Vec::new(),
-            };
-
-            objects.insert(id, object);
+// a page or database as returned by search or retrieve
+async fn parse_object(client: &Client, result: &Value, block_pages: &mut BlockPages) -> Object {
+    let parent_id = get_parent_id(client, &result["parent"], block_pages)
+        .await
+        .unwrap_or_default();
+
+    let object_type = result["object"].as_str().unwrap_or_default().to_string();
+    let title = match get_title(object_type.clone(), result) {
+        Ok(title) => title,
+        Err(_) => "Untitled".to_string(),
+    };
+
+    Object {
+        id: result["id"].as_str().unwrap_or_default().to_string(),
+        parent_id,
+        title,
+        object_type,
+        children: Vec::new(),
+        last_edited_time: result["last_edited_time"].as_str().map(|time| time.to_string()),
+    }
+}
+
+pub const WORKSPACE_CAP: usize = 1000;
+
+// notion rejects page sizes above 100
+fn page_size() -> usize {
+    get_config().search_page_size.clamp(1, 100)
+}
+
+// walks the search endpoint one page at a time following `next_cursor`
+pub struct SearchPaginator {
+    client: Client,
+    query: String,
+    cursor: Option<String>,
+    has_more: bool,
+    fetched: usize,
+    cap: usize,
+    block_pages: BlockPages,
+}
+
+impl SearchPaginator {
+    pub fn new(query: Option<String>, cap: usize) -> Self {
+        Self {
+            client: Client::new(),
+            query: query.unwrap_or_default(),
+            cursor: None,
+            has_more: true,
+            fetched: 0,
+            cap,
+            block_pages: HashMap::new(),
         }
     }
 
+    pub fn is_done(&self) -> bool {
+        !self.has_more || self.fetched >= self.cap
+    }
+
+    pub async fn next_page(&mut self) -> Result<IndexMap<String, Object>, Box<dyn Error>> {
+        let mut objects = IndexMap::<String, Object>::new();
+        if self.is_done() {
+            return Ok(objects);
+        }
+
+        // newest first, so the cap drops the objects edited longest ago
+        let mut body = serde_json::json!({  "query": self.query, "page_size": page_size().min(self.cap - self.fetched), "sort":{
+          "direction":"descending",
+          "timestamp":"last_edited_time"
+        }});
+        if let Some(cursor) = &self.cursor {
+            body["start_cursor"] = Value::String(cursor.clone());
+        }
+
+        let response = self
+            .client
+            .post("https://api.notion.com/v1/search")
+            .header("Authorization", format!("Bearer {}", get_access_token()))
+            .header("Notion-Version", notion_version())
+            .json(&body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error("search", response).await);
+        }
+        let json_data = response.json::<Value>().await?;
+
+        self.has_more = json_data["has_more"].as_bool().unwrap_or(false);
+        self.cursor = json_data["next_cursor"].as_str().map(|cursor| cursor.to_string());
+
+        if let Some(results) = json_data["results"].as_array() {
+            for result in results {
+                let object = parse_object(&self.client, result, &mut self.block_pages).await;
+                objects.insert(object.id.clone(), object);
+            }
+            self.fetched += results.len();
+        }
+
+        Ok(objects)
+    }
+}
+
+// every result for a query, up to `WORKSPACE_CAP` objects
+pub async fn search_api(query: Option<String>) -> Result<IndexMap<String, Object>, Box<dyn Error>> {
+    let mut paginator = SearchPaginator::new(query, WORKSPACE_CAP);
+    let mut objects = IndexMap::<String, Object>::new();
+    while !paginator.is_done() {
+        objects.extend(paginator.next_page().await?);
+    }
     Ok(objects)
 }
+
+// nest objects under their parents, returning the top level objects
+pub fn build_tree(objects: &IndexMap<String, Object>) -> Vec<Object> {
+    let mut children = HashMap::<String, Vec<&Object>>::new();
+    let mut roots = Vec::<&Object>::new();
+
+    for object in objects.values() {
+        match &object.parent_id {
+            Some(parent_id) if objects.contains_key(parent_id) => {
+                children.entry(parent_id.clone()).or_default().push(object);
+            }
+            _ => roots.push(object),
+        }
+    }
+
+    fn attach(object: &Object, children: &HashMap<String, Vec<&Object>>) -> Object {
+        let mut node = object.clone();
+        node.children = children
+            .get(&object.id)
+            .map(|items| items.iter().map(|child| attach(child, children)).collect())
+            .unwrap_or_default();
+        node.children.sort_by_key(|child| child.title.to_lowercase());
+        node
+    }
+
+    let mut tree: Vec<Object> = roots.iter().map(|root| attach(root, &children)).collect();
+    tree.sort_by_key(|object| object.title.to_lowercase());
+    tree
+}
+
+// flatten the tree into a list of every object it contains
+pub fn flatten_tree(tree: &[Object]) -> Vec<Object> {
+    let mut objects = Vec::new();
+    for object in tree {
+        objects.push(object.clone());
+        objects.extend(flatten_tree(&object.children));
+    }
+    objects
+}
+
+// flatten the tree into a list of every database it contains
+pub fn collect_databases(tree: &[Object]) -> Vec<Object> {
+    let mut databases = Vec::new();
+    for object in tree {
+        if object.object_type == "database" {
+            databases.push(object.clone());
+        }
+        databases.extend(collect_databases(&object.children));
+    }
+    databases
+}
+
+// depth first lookup of an object by its id
+pub fn find_by_id<'a>(tree: &'a [Object], id: &str) -> Option<&'a Object> {
+    for object in tree {
+        if object.id == id {
+            return Some(object);
+        }
+        if let Some(found) = find_by_id(&object.children, id) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+// add a newly created object under its parent so it shows without refetching the tree
+pub fn insert_child(tree: &mut [Object], object: Object) -> Option<Object> {
+    let mut object = object;
+    for parent in tree.iter_mut() {
+        if object.parent_id.as_deref() == Some(parent.id.as_str()) {
+            parent.children.push(object);
+            return None;
+        }
+        object = insert_child(&mut parent.children, object)?;
+    }
+    Some(object)
+}
+
+// take an object out of the tree wherever it is nested
+pub fn remove_object(tree: &mut Vec<Object>, id: &str) -> Option<Object> {
+    if let Some(index) = tree.iter().position(|object| object.id == id) {
+        return Some(tree.remove(index));
+    }
+    tree.iter_mut().find_map(|parent| remove_object(&mut parent.children, id))
+}
+
+// notion ids are 32 hex digits, with or without dashes
+pub fn is_notion_id(reference: &str) -> bool {
+    let hex: String = reference.chars().filter(|c| *c != '-').collect();
+    hex.len() == 32 && hex.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+// fetch a page by id, falling back to a database with that id
+pub async fn get_object(id: &str) -> Result<Object, Box<dyn Error>> {
+    let client = Client::new();
+
+    for endpoint in ["pages", "databases"] {
+        let response = client
+            .get(format!("https://api.notion.com/v1/{}/{}", endpoint, id))
+            .header("Authorization", format!("Bearer {}", get_access_token()))
+            .header("Notion-Version", notion_version())
+            .send()
+            .await?;
+
+        if response.status().is_success() {
+            let result = response.json::<serde_json::Value>().await?;
+            return Ok(parse_object(&client, &result, &mut HashMap::new()).await);
+        }
+    }
+
+    Err(format!("no page or database with id {}", id).into())
+}
+
+async fn find_matching(reference: &str, object_type: Option<&str>) -> Result<Object, Box<dyn Error>> {
+    if is_notion_id(reference) {
+        return get_object(reference).await;
+    }
+
+    let results = search_api(Some(reference.to_string())).await;
+    let matches = match_search(reference, &results);
+    results?;
+    let mut candidates = matches
+        .values()
+        .filter(|object| object_type.is_none_or(|object_type| object.object_type == object_type));
+    candidates
+        .clone()
+        .find(|object| object.title.eq_ignore_ascii_case(reference))
+        .or_else(|| candidates.next())
+        .cloned()
+        .ok_or_else(|| format!("nothing in the workspace matches \"{}\"", reference).into())
+}
+
+// resolve an id or a title that matches exactly one object, for commands that
+// change things and can't act on a best guess
+pub async fn find_exact(reference: &str) -> Result<Object, Box<dyn Error>> {
+    if is_notion_id(reference) {
+        return get_object(reference).await;
+    }
+
+    let results = search_api(Some(reference.to_string())).await?;
+    let titled: Vec<&Object> = results.values().filter(|object| object.title == reference).collect();
+    match titled.as_slice() {
+        [object] => Ok((*object).clone()),
+        [] => Err(format!("nothing is titled exactly \"{}\", give its id or full title", reference).into()),
+        objects => {
+            let ids: Vec<String> = objects
+                .iter()
+                .map(|object| format!("  {}\t{}", object.id, object.object_type))
+                .collect();
+            Err(format!(
+                "{} objects are titled \"{}\", give an id instead:\n{}",
+                objects.len(),
+                reference,
+                ids.join("\n")
+            )
+            .into())
+        }
+    }
+}
+
+// resolve an id or a title to an object, preferring an exact title match
+pub async fn find_object(reference: &str) -> Result<Object, Box<dyn Error>> {
+    find_matching(reference, None).await
+}
+
+// like find_object, but only databases are considered when matching titles
+pub async fn find_database(reference: &str) -> Result<Object, Box<dyn Error>> {
+    let object = find_matching(reference, Some("database")).await?;
+    if object.object_type != "database" {
+        return Err(format!("\"{}\" is a page, not a database", object.title).into());
+    }
+    Ok(object)
+}
@@ -0,0 +1,94 @@
+use std::{collections::HashMap, error::Error};
+
+use chrono::{Local, NaiveDate};
+use serde_json::Value;
+
+use crate::{
+    api::{
+        blocks::{append_block_children, get_block_children},
+        databases::create_titled_row,
+        pages::create_page,
+        search::Object,
+    },
+    database::templates::{get_template, Template},
+    utils::markdown::{blocks_to_markdown, escape_markdown, parse_markdown},
+};
+
+// template markdown with its placeholders filled in, as blocks to append. the values
+// are escaped so a title like `# Retro` stays text instead of reshaping the template
+pub fn fill_template(body: &str, date: NaiveDate, title: &str) -> Vec<Value> {
+    parse_markdown(
+        &body
+            .replace("{{date}}", &escape_markdown(&date.to_string()))
+            .replace("{{title}}", &escape_markdown(title)),
+    )
+}
+
+pub fn find_template(name: &str) -> Result<Template, Box<dyn Error>> {
+    get_template(name)?.ok_or_else(|| {
+        format!("no template named \"{}\", see notion_cli template list", name).into()
+    })
+}
+
+// a page's content as template markdown, subpages are kept as links
+pub async fn capture_page(page_id: &str) -> Result<String, Box<dyn Error>> {
+    let blocks = get_block_children(page_id).await?;
+    Ok(blocks_to_markdown(&blocks, &HashMap::new()))
+}
+
+// a page under a page or a row in a database, filled from the template when given
+pub async fn create_from_template(
+    parent: &Object,
+    title: &str,
+    template: Option<&Template>,
+) -> Result<Object, Box<dyn Error>> {
+    let page = if parent.object_type == "database" {
+        create_titled_row(&parent.id, title).await?
+    } else {
+        create_page(&parent.id, title).await?
+    };
+
+    if let Some(template) = template {
+        let blocks = fill_template(&template.body, Local::now().date_naive(), title);
+        if !blocks.is_empty() {
+            append_block_children(&page.id, None, blocks).await?;
+        }
+    }
+    Ok(page)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(block: &Value) -> String {
+        let block_type = block["type"].as_str().unwrap_or("");
+        block[block_type]["rich_text"]
+            .as_array()
+            .map(|items| items.iter().filter_map(|item| item["text"]["content"].as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn placeholders_are_filled_as_text() {
+        let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        let blocks = fill_template("## {{title}}\n\n{{title}}\n\n- due {{date}}", date, "*draft* [v2]");
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0]["type"], "heading_2");
+        assert_eq!(text(&blocks[0]), "*draft* [v2]");
+        assert_eq!(blocks[0]["heading_2"]["rich_text"][0]["annotations"]["italic"], false);
+        assert_eq!(text(&blocks[1]), "*draft* [v2]");
+        assert_eq!(text(&blocks[2]), "due 2024-05-01");
+    }
+
+    #[test]
+    fn titles_keep_the_template_shape() {
+        let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        for title in ["# Retro", "- item", "1. first", "> quote"] {
+            let blocks = fill_template("{{title}}", date, title);
+            assert_eq!(blocks.len(), 1);
+            assert_eq!(blocks[0]["type"], "paragraph", "{}", title);
+            assert_eq!(text(&blocks[0]), title);
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use std::error::Error;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::api::{
+    blocks::{get_block_children, update_block, Block},
+    databases::{get_columns, property_text, query_database_filtered, update_properties, Column},
+    search::Object,
+};
+
+// what checking a to-do off changes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Check {
+    // a to_do block in a page
+    Block,
+    // a database row, by ticking its checkbox
+    Checkbox { property: String },
+    // a database row, by setting its status to the first complete option
+    Status { property: String, done: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Todo {
+    pub id: String,
+    pub text: String,
+    // the page or database it came from, the list is grouped by it
+    pub source: Object,
+    pub check: Check,
+}
+
+impl Todo {
+    // the page to open for it, the block's page or the row itself
+    pub fn page(&self) -> Object {
+        match self.check {
+            Check::Block => self.source.clone(),
+            _ => Object {
+                id: self.id.clone(),
+                parent_id: Some(self.source.id.clone()),
+                title: self.text.clone(),
+                object_type: "page".to_string(),
+                children: Vec::new(),
+                last_edited_time: None,
+            },
+        }
+    }
+}
+
+// unchecked to_do blocks anywhere in the tree, including under toggles and other to-dos
+fn unchecked(blocks: &[Block], page: &Object, todos: &mut Vec<Todo>) {
+    for block in blocks {
+        if block.block_type == "to_do" && block.checked != Some(true) {
+            todos.push(Todo {
+                id: block.id.clone(),
+                text: block.text.iter().map(|span| span.text.as_str()).collect(),
+                source: page.clone(),
+                check: Check::Block,
+            });
+        }
+        unchecked(&block.children, page, todos);
+    }
+}
+
+async fn page_todos(page: &Object) -> Result<Vec<Todo>, Box<dyn Error>> {
+    let blocks = get_block_children(&page.id).await?;
+    let mut todos = Vec::new();
+    unchecked(&blocks, page, &mut todos);
+    Ok(todos)
+}
+
+// rows with a status outside the complete group, or else with the checkbox clear.
+// a status with a complete group says when a row is finished, a checkbox could be
+// anything, so one is only used without the other
+async fn database_todos(database: &Object) -> Result<Vec<Todo>, Box<dyn Error>> {
+    let columns = get_columns(&database.id).await?;
+    let status = columns
+        .iter()
+        .find(|column| column.property_type == "status" && !column.done.is_empty());
+    let checkboxes: Vec<&Column> = columns
+        .iter()
+        .filter(|column| column.property_type == "checkbox")
+        .collect();
+    // with several checkboxes, one named like done is the likeliest
+    let checkbox = checkboxes
+        .iter()
+        .find(|column| matches!(column.name.to_lowercase().as_str(), "done" | "complete" | "completed" | "checked"))
+        .or(checkboxes.first());
+
+    let (filter, check) = match (status, checkbox) {
+        (Some(column), _) => (
+            json!({ "and": column
+                .done
+                .iter()
+                .map(|done| json!({ "property": column.name, "status": { "does_not_equal": done } }))
+                .collect::<Vec<Value>>() }),
+            Check::Status { property: column.name.clone(), done: column.done[0].clone() },
+        ),
+        (None, Some(column)) => (
+            json!({ "property": column.name, "checkbox": { "equals": false } }),
+            Check::Checkbox { property: column.name.clone() },
+        ),
+        (None, None) => {
+            return Err(format!("{} has no status or checkbox property", database.title).into());
+        }
+    };
+
+    let title = columns.first().map(|column| column.name.clone()).unwrap_or_default();
+    let rows = query_database_filtered(&database.id, filter, Value::Null).await?;
+    Ok(rows
+        .iter()
+        .map(|row| Todo {
+            id: row.id.clone(),
+            text: property_text(&row.properties[&title]),
+            source: database.clone(),
+            check: check.clone(),
+        })
+        .collect())
+}
+
+// every open to-do across the sources in their order, with an error for each one that failed
+pub async fn collect_todos(sources: &[Object]) -> (Vec<Todo>, Vec<String>) {
+    let mut todos = Vec::new();
+    let mut errors = Vec::new();
+    for source in sources {
+        let found = if source.object_type == "database" {
+            database_todos(source).await
+        } else {
+            page_todos(source).await
+        };
+        match found {
+            Ok(found) => todos.extend(found),
+            Err(e) => errors.push(format!("{}: {}", source.title, e)),
+        }
+    }
+    (todos, errors)
+}
+
+pub async fn check_off(todo: &Todo) -> Result<(), Box<dyn Error>> {
+    match &todo.check {
+        Check::Block => {
+            update_block(&todo.id, &json!({ "type": "to_do", "to_do": { "checked": true } })).await
+        }
+        Check::Checkbox { property } => {
+            update_properties(&todo.id, json!({ property: { "checkbox": true } })).await?;
+            Ok(())
+        }
+        Check::Status { property, done } => {
+            update_properties(&todo.id, json!({ property: { "status": { "name": done } } })).await?;
+            Ok(())
+        }
+    }
+}
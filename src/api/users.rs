@@ -0,0 +1,55 @@
+use std::error::Error;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{api::api_error, database::user::get_access_token, utils::config::notion_version};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Person {
+    pub id: String,
+    pub name: String,
+    pub email: Option<String>,
+}
+
+// every member of the workspace, bots left out
+pub async fn get_people() -> Result<Vec<Person>, Box<dyn Error>> {
+    let client = Client::new();
+    let mut people = Vec::<Person>::new();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let mut request = client
+            .get("https://api.notion.com/v1/users")
+            .header("Authorization", format!("Bearer {}", get_access_token()))
+            .header("Notion-Version", notion_version())
+            .query(&[("page_size", "100")]);
+        if let Some(cursor) = &cursor {
+            request = request.query(&[("start_cursor", cursor)]);
+        }
+
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(api_error("get users", response).await);
+        }
+        let json_data = response.json::<Value>().await?;
+
+        if let Some(results) = json_data["results"].as_array() {
+            for result in results.iter().filter(|result| result["type"] == "person") {
+                people.push(Person {
+                    id: result["id"].as_str().unwrap_or("").to_string(),
+                    name: result["name"].as_str().unwrap_or("").to_string(),
+                    email: result["person"]["email"].as_str().map(|email| email.to_string()),
+                });
+            }
+        }
+
+        cursor = json_data["next_cursor"].as_str().map(|cursor| cursor.to_string());
+        if !json_data["has_more"].as_bool().unwrap_or(false) || cursor.is_none() {
+            break;
+        }
+    }
+
+    Ok(people)
+}
@@ -0,0 +1,815 @@
+use std::{error::Error, sync::Mutex};
+
+use chrono::Local;
+use crossterm::event::KeyEvent;
+use serde_json::{json, Value};
+
+use crate::{
+    api::{
+        auth::logout,
+        databases::{get_columns, query_database_filtered, update_properties},
+        journal::journal_entry,
+        pages::{move_page, set_archived},
+        search::{build_tree, collect_databases, insert_child, remove_object, search_api, Object},
+        templates::{create_from_template, find_template},
+        todos::{check_off, collect_todos, Todo},
+        users::get_people,
+    },
+    components::{controls::controls, search::Search},
+    database::{
+        archived::{record_archived, remove_archived},
+        favorites::{get_favorites, Favorite},
+        recents::record_recent,
+        todo_sources::get_todo_sources,
+    },
+    utils::{
+        config::get_config,
+        edit::{run_editor, EditSession},
+        keymap::{lookup, Action, Mode},
+        query::parse_query,
+    },
+    views::{
+        auth::AuthView,
+        board::Board,
+        calendar::Calendar,
+        favorites::FavoritesView,
+        grid::Grid,
+        help::help_view,
+        home::HomeView,
+        login::LoginView,
+        pages::PagesView,
+        reader::Reader,
+        row::RowEditor,
+        recent::Recents,
+        settings::Settings,
+        tables::TablesView,
+        todos::TodosView,
+        trash::TrashView,
+        Command, Layout, View,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Section {
+    Login,
+    Auth,
+    Home,
+    Pages,
+    Tables,
+    Favorites,
+    Recents,
+    Trash,
+    Todos,
+    Settings,
+    Reader,
+    Grid,
+    Board,
+    Calendar,
+    Row,
+}
+
+impl Section {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Section::Login => "login",
+            Section::Auth => "auth",
+            Section::Home => "home",
+            Section::Pages => "pages",
+            Section::Tables => "tables",
+            Section::Favorites => "favorites",
+            Section::Recents => "recents",
+            Section::Trash => "trash",
+            Section::Todos => "todos",
+            Section::Settings => "settings",
+            Section::Reader => "reader",
+            Section::Grid => "grid",
+            Section::Board => "board",
+            Section::Calendar => "calendar",
+            Section::Row => "row",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Section> {
+        [
+            Section::Login,
+            Section::Auth,
+            Section::Home,
+            Section::Pages,
+            Section::Tables,
+            Section::Favorites,
+            Section::Recents,
+            Section::Trash,
+            Section::Todos,
+            Section::Settings,
+            Section::Reader,
+            Section::Grid,
+            Section::Board,
+            Section::Calendar,
+            Section::Row,
+        ]
+        .into_iter()
+        .find(|section| section.name() == name)
+    }
+}
+
+lazy_static::lazy_static! (
+    pub static ref AUTHENTICATED: Mutex<bool> = Mutex::new(false);
+    // auth finishes on the oauth callback task, the app picks it up on its next tick
+    static ref PENDING_SECTION: Mutex<Option<Section>> = Mutex::new(None);
+);
+
+pub fn auth_completed() {
+    *AUTHENTICATED.lock().unwrap() = true;
+    let start = Section::from_name(&get_config().start_section).unwrap_or(Section::Home);
+    *PENDING_SECTION.lock().unwrap() = Some(start);
+}
+
+pub fn logout_completed() {
+    *AUTHENTICATED.lock().unwrap() = false;
+    *PENDING_SECTION.lock().unwrap() = Some(Section::Login);
+}
+
+// notion data shared between views
+#[derive(Default)]
+pub struct Workspace {
+    pub tree: Vec<Object>,
+    pub tables: Vec<Object>,
+    pub favorites: Vec<Favorite>,
+}
+
+impl Workspace {
+    pub async fn load_tree(&mut self) -> Result<(), Box<dyn Error>> {
+        self.tree = build_tree(&search_api(None).await?);
+        self.tables = collect_databases(&self.tree);
+        Ok(())
+    }
+
+    pub fn reload_favorites(&mut self) {
+        self.favorites = get_favorites().unwrap_or_default();
+    }
+}
+
+pub struct App {
+    section: Section,
+    search: Search,
+    workspace: Workspace,
+    login: LoginView,
+    auth: AuthView,
+    home: HomeView,
+    pages: PagesView,
+    tables: TablesView,
+    favorites: FavoritesView,
+    recents: Recents,
+    trash: TrashView,
+    todos: TodosView,
+    settings: Settings,
+    reader: Option<Reader>,
+    grid: Option<Grid>,
+    board: Option<Board>,
+    calendar: Option<Calendar>,
+    row: Option<RowEditor>,
+    help_open: bool,
+}
+
+impl App {
+    pub async fn new() -> Self {
+        // the tree is loaded when a section first needs it, after login
+        let mut workspace = Workspace::default();
+        workspace.reload_favorites();
+
+        let mut recents = Recents::default();
+        recents.load(&workspace.tree);
+
+        let mut app = Self {
+            section: Section::Login,
+            search: Search::new(),
+            workspace,
+            login: LoginView,
+            auth: AuthView,
+            home: HomeView,
+            pages: PagesView::default(),
+            tables: TablesView::default(),
+            favorites: FavoritesView::default(),
+            recents,
+            trash: TrashView::default(),
+            todos: TodosView::default(),
+            settings: Settings::default(),
+            reader: None,
+            grid: None,
+            board: None,
+            calendar: None,
+            row: None,
+            help_open: false,
+        };
+        if let Some(section) = PENDING_SECTION.lock().unwrap().take() {
+            app.section = section;
+        }
+        app
+    }
+
+    fn view(&self) -> Option<&dyn View> {
+        match self.section {
+            Section::Login => Some(&self.login),
+            Section::Auth => Some(&self.auth),
+            Section::Home => Some(&self.home),
+            Section::Pages => Some(&self.pages),
+            Section::Tables => Some(&self.tables),
+            Section::Favorites => Some(&self.favorites),
+            Section::Recents => Some(&self.recents),
+            Section::Trash => Some(&self.trash),
+            Section::Todos => Some(&self.todos),
+            Section::Settings => Some(&self.settings),
+            Section::Reader => self.reader.as_ref().map(|reader| reader as &dyn View),
+            Section::Grid => self.grid.as_ref().map(|grid| grid as &dyn View),
+            Section::Board => self.board.as_ref().map(|board| board as &dyn View),
+            Section::Calendar => self.calendar.as_ref().map(|calendar| calendar as &dyn View),
+            Section::Row => self.row.as_ref().map(|row| row as &dyn View),
+        }
+    }
+
+    fn view_mut(&mut self) -> (Option<&mut dyn View>, &mut Workspace) {
+        let view: Option<&mut dyn View> = match self.section {
+            Section::Login => Some(&mut self.login),
+            Section::Auth => Some(&mut self.auth),
+            Section::Home => Some(&mut self.home),
+            Section::Pages => Some(&mut self.pages),
+            Section::Tables => Some(&mut self.tables),
+            Section::Favorites => Some(&mut self.favorites),
+            Section::Recents => Some(&mut self.recents),
+            Section::Trash => Some(&mut self.trash),
+            Section::Todos => Some(&mut self.todos),
+            Section::Settings => Some(&mut self.settings),
+            Section::Reader => self.reader.as_mut().map(|reader| reader as &mut dyn View),
+            Section::Grid => self.grid.as_mut().map(|grid| grid as &mut dyn View),
+            Section::Board => self.board.as_mut().map(|board| board as &mut dyn View),
+            Section::Calendar => self.calendar.as_mut().map(|calendar| calendar as &mut dyn View),
+            Section::Row => self.row.as_mut().map(|row| row as &mut dyn View),
+        };
+        (view, &mut self.workspace)
+    }
+
+    fn mode(&self) -> Mode {
+        if self.search.enabled {
+            Mode::Search
+        } else if self.view().is_some_and(|view| view.editing()) {
+            Mode::Editing
+        } else {
+            Mode::Normal
+        }
+    }
+
+    pub async fn render(&self) {
+        controls(self.search.enabled, true);
+        self.search.render(false).await;
+        self.render_content();
+        if self.help_open {
+            self.render_help();
+        }
+    }
+
+    fn render_content(&self) {
+        if self.search.enabled {
+            return;
+        }
+        if let Some(view) = self.view() {
+            view.render(&Layout::current(), &self.workspace);
+        }
+    }
+
+    fn render_help(&self) {
+        let layout = Layout::current();
+        help_view(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_search,
+            self.section,
+        );
+    }
+
+    // apply a login or logout that finished since the last tick
+    pub async fn sync_session(&mut self) {
+        let section = match PENDING_SECTION.lock().unwrap().take() {
+            Some(section) => section,
+            None => return,
+        };
+        if section == Section::Login {
+            self.workspace = Workspace::default();
+            self.recents = Recents::default();
+        }
+        self.switch(section).await;
+    }
+
+    // jump to a section from the menu, resetting its view
+    async fn switch(&mut self, section: Section) {
+        let needs_tree = matches!(section, Section::Pages | Section::Tables)
+            || (section == Section::Recents && self.recents.merged);
+        let loaded = match needs_tree && self.workspace.tree.is_empty() {
+            true => self.workspace.load_tree().await,
+            false => Ok(()),
+        };
+        self.section = section;
+        let (view, workspace) = self.view_mut();
+        if let Some(view) = view {
+            view.enter(workspace);
+        }
+        // an empty tree is tried again the next time the section is opened
+        if let Err(e) = loaded {
+            let message = format!("failed to load the workspace: {}", e);
+            match section {
+                Section::Pages => self.pages.message = Some(message),
+                Section::Tables => self.tables.message = Some(message),
+                // recents still show what was opened from here
+                _ => {}
+            }
+        }
+        self.render_content();
+    }
+
+    // switch to the reader or the grid and load the object into it
+    async fn open(&mut self, object: Object) {
+        record_recent(&object);
+        let return_section = self.section;
+
+        if object.object_type == "page" {
+            self.reader = Some(Reader::new(object, return_section));
+            self.section = Section::Reader;
+            self.render_content();
+            if let Some(reader) = self.reader.as_mut() {
+                reader.fetch().await;
+            }
+        } else {
+            self.grid = Some(Grid::new(object, return_section));
+            self.section = Section::Grid;
+            self.render_content();
+            if let Some(grid) = self.grid.as_mut() {
+                let _ = grid.fetch(&self.workspace.tree).await;
+            }
+        }
+
+        self.recents.load(&self.workspace.tree);
+        self.render_content();
+    }
+
+    async fn create(&mut self, parent: Object, title: String, template: Option<String>) {
+        let created = match template.as_deref().map(find_template).transpose() {
+            Ok(template) => create_from_template(&parent, &title, template.as_ref()).await,
+            Err(e) => Err(e),
+        };
+
+        self.pages.message = Some(match created {
+            Ok(page) => {
+                insert_child(&mut self.workspace.tree, page);
+                // a new child of a top level item is shown highlighted in the right column
+                if let Some(index) = self.workspace.tree.get(self.pages.pos).and_then(|top| {
+                    (top.id == parent.id).then(|| top.children.len().saturating_sub(1))
+                }) {
+                    self.pages.selected = true;
+                    self.pages.child_pos = index;
+                }
+                format!("created \"{}\" in {}", title, parent.title)
+            }
+            Err(e) => format!("failed to create \"{}\": {}", title, e),
+        });
+        self.render_content();
+    }
+
+    // open today's journal entry, creating it when it doesn't exist yet
+    async fn journal(&mut self) {
+        match journal_entry(Local::now().date_naive()).await {
+            Ok((entry, created)) => {
+                if created {
+                    insert_child(&mut self.workspace.tree, entry.clone());
+                }
+                self.open(entry).await;
+                if created {
+                    self.set_reader_status("new entry".to_string());
+                    self.render_content();
+                }
+            }
+            Err(e) => {
+                // shown in the reader without recording a recent
+                let placeholder = Object {
+                    id: String::new(),
+                    parent_id: None,
+                    title: "journal".to_string(),
+                    object_type: "page".to_string(),
+                    children: Vec::new(),
+                    last_edited_time: None,
+                };
+                let mut reader = Reader::new(placeholder, self.section);
+                reader.message = Some(e.to_string());
+                self.reader = Some(reader);
+                self.section = Section::Reader;
+                self.render_content();
+            }
+        }
+    }
+
+    // gather the open to-dos, which takes a request per source
+    async fn todos(&mut self) {
+        self.section = Section::Todos;
+        self.todos.message = Some("loading...".to_string());
+        self.render_content();
+
+        let sources = get_todo_sources().unwrap_or_default();
+        let (todos, errors) = collect_todos(&sources).await;
+        self.todos.load(todos);
+        self.todos.message = match errors.first() {
+            Some(error) if errors.len() > 1 => Some(format!("{} (and {} more failed)", error, errors.len() - 1)),
+            Some(error) => Some(error.clone()),
+            None if sources.is_empty() => None,
+            None => Some(format!("{} open from {} sources", self.todos.todos.len(), sources.len())),
+        };
+        self.render_content();
+    }
+
+    async fn check(&mut self, todo: Todo) {
+        self.render_content();
+        self.todos.message = Some(match check_off(&todo).await {
+            Ok(()) => {
+                self.todos.remove(&todo.id);
+                format!("checked off {}", todo.text)
+            }
+            Err(e) => format!("failed to check off {}: {}", todo.text, e),
+        });
+        self.render_content();
+    }
+
+    // the workspace tree changed shape, keep the lists that are built from it in step
+    fn tree_changed(&mut self) {
+        self.workspace.tables = collect_databases(&self.workspace.tree);
+        self.pages.clamp(&self.workspace.tree);
+    }
+
+    // trash a page, database or row and drop it from wherever it is shown
+    async fn archive(&mut self, object: Object) {
+        let message = match set_archived(&object, true).await {
+            Ok(()) => {
+                record_archived(&object);
+                remove_object(&mut self.workspace.tree, &object.id);
+                self.tree_changed();
+                if let Some(grid) = self.grid.as_mut() {
+                    grid.remove_row(&object.id);
+                }
+                format!("archived {}, [a] to restore", object.title)
+            }
+            Err(e) => format!("failed to archive {}: {}", object.title, e),
+        };
+
+        match (self.section, self.grid.as_mut()) {
+            (Section::Grid, Some(grid)) => grid.status = Some(message),
+            _ => self.pages.message = Some(message),
+        }
+        self.render_content();
+    }
+
+    async fn restore(&mut self, object: Object) {
+        self.trash.message = Some(match set_archived(&object, false).await {
+            Ok(()) => {
+                remove_archived(&object.id);
+                // top level items have no parent to go under
+                if let Some(object) = insert_child(&mut self.workspace.tree, object.clone()) {
+                    if object.parent_id.is_none() {
+                        self.workspace.tree.push(object);
+                    }
+                }
+                self.tree_changed();
+                self.trash.load();
+                format!("restored {}", object.title)
+            }
+            Err(e) => format!("failed to restore {}: {}", object.title, e),
+        });
+        self.render_content();
+    }
+
+    async fn move_page(&mut self, page: Object, parent: Object) {
+        let moved = if parent.id == page.id {
+            Err("a page can't be moved into itself".into())
+        } else {
+            move_page(&page.id, &parent).await
+        };
+
+        self.pages.message = Some(match moved {
+            Ok(()) => {
+                if let Some(mut object) = remove_object(&mut self.workspace.tree, &page.id) {
+                    object.parent_id = Some(parent.id.clone());
+                    insert_child(&mut self.workspace.tree, object);
+                }
+                self.tree_changed();
+                format!("moved {} to {}", page.title, parent.title)
+            }
+            Err(e) => format!("failed to move {}: {}", page.title, e),
+        });
+        self.render_content();
+    }
+
+    // the grid's rows as a board, loading the database into the grid first when it isn't there
+    async fn board(&mut self, database: Object) {
+        let return_section = self.section;
+        // a grid with no columns never loaded, so it's fetched again
+        if self.grid.as_ref().is_none_or(|grid| grid.database.id != database.id || grid.columns.is_empty()) {
+            let mut grid = Grid::new(database, return_section);
+            let loaded = grid.fetch(&self.workspace.tree).await;
+            self.grid = Some(grid);
+            if loaded.is_err() {
+                self.section = Section::Grid;
+                return self.render_content();
+            }
+        }
+        let Some(grid) = self.grid.as_mut() else {
+            return;
+        };
+
+        let titles = grid.cells.iter().map(|cells| cells[0].clone()).collect();
+        match Board::new(grid.database.clone(), grid.columns.clone(), grid.rows.clone(), titles, return_section) {
+            Some(board) => {
+                self.board = Some(board);
+                self.section = Section::Board;
+            }
+            None => {
+                grid.status = Some("no select or status property to group by".to_string());
+                self.section = Section::Grid;
+            }
+        }
+        self.render_content();
+    }
+
+    // the database by date, or the grid saying why not when it has no date property
+    async fn calendar(&mut self, database: Object) {
+        let loaded = self
+            .grid
+            .as_ref()
+            .filter(|grid| grid.database.id == database.id && !grid.columns.is_empty());
+        let columns = match loaded {
+            Some(grid) => Ok(grid.columns.clone()),
+            None => get_columns(&database.id).await,
+        };
+        let columns = match columns {
+            Ok(columns) => columns,
+            Err(e) => {
+                let message = format!("failed to load {}: {}", database.title, e);
+                match (self.section, self.grid.as_mut()) {
+                    (Section::Grid, Some(grid)) => grid.status = Some(message),
+                    _ => self.pages.message = Some(message),
+                }
+                return self.render_content();
+            }
+        };
+
+        match Calendar::new(database.clone(), columns, self.section) {
+            Some(calendar) => {
+                self.calendar = Some(calendar);
+                self.section = Section::Calendar;
+                self.render_content();
+                if let Some(calendar) = self.calendar.as_mut() {
+                    calendar.fetch().await;
+                }
+            }
+            None => {
+                if self.section != Section::Grid {
+                    self.open(database).await;
+                }
+                if let Some(grid) = self.grid.as_mut() {
+                    grid.status = Some("no date property for a calendar".to_string());
+                }
+            }
+        }
+        self.render_content();
+    }
+
+    // the selected grid row as a form, with the member list when a column holds people
+    async fn edit_row(&mut self) {
+        let Some(grid) = self.grid.as_ref() else {
+            return;
+        };
+        let (Some(row), Some(values)) = (grid.selected_row().cloned(), grid.cells.get(grid.row_pos).cloned()) else {
+            return;
+        };
+        let people = if grid.columns.iter().any(|column| column.property_type == "people") {
+            get_people().await.unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        self.row = Some(RowEditor::new(
+            grid.database.clone(),
+            grid.columns.clone(),
+            row,
+            values,
+            people,
+            Section::Grid,
+        ));
+        self.section = Section::Row;
+        self.render_content();
+    }
+
+    // the editor already shows the new value, undo it there if notion refuses
+    async fn set_property(&mut self, id: String, column: String, value: Value) {
+        self.render_content();
+
+        let result = update_properties(&id, json!({ column: value })).await;
+        let cells = match &result {
+            Ok(properties) => self
+                .grid
+                .as_mut()
+                .and_then(|grid| grid.update_row(&id, properties.clone(), &self.workspace.tree)),
+            Err(_) => None,
+        };
+        if let (Section::Board, Some(board)) = (self.section, self.board.as_mut()) {
+            match result {
+                Ok(properties) => board.confirm(&id, properties),
+                Err(e) => board.rollback(e.to_string()),
+            }
+        } else if let Some(editor) = self.row.as_mut() {
+            match (result, cells) {
+                (Ok(properties), Some(cells)) => editor.confirm(properties, cells),
+                (Ok(_), None) => editor.status = Some("saved".to_string()),
+                (Err(e), _) => editor.rollback(e.to_string()),
+            }
+        }
+        self.render_content();
+    }
+
+    // rows matching the query, leaving the grid as it was when it doesn't parse
+    async fn query(&mut self, text: String) {
+        let Some(grid) = self.grid.as_mut() else {
+            return;
+        };
+        let query = match parse_query(&text, &grid.columns) {
+            Ok(query) => query,
+            Err(e) => {
+                grid.status = Some(e);
+                return self.render_content();
+            }
+        };
+
+        grid.status = Some("querying...".to_string());
+        self.render_content();
+
+        let Some(grid) = self.grid.as_mut() else {
+            return;
+        };
+        match query_database_filtered(&grid.database.id, query.filter, query.sorts).await {
+            Ok(rows) => {
+                grid.status = Some(format!("{} rows", rows.len()));
+                grid.load(grid.columns.clone(), rows, &self.workspace.tree);
+                grid.query = text;
+                grid.row_pos = 0;
+            }
+            Err(e) => grid.status = Some(e.to_string()),
+        }
+        self.render_content();
+    }
+
+    fn set_reader_status(&mut self, status: String) {
+        if let Some(reader) = self.reader.as_mut() {
+            reader.status = Some(status);
+        }
+    }
+
+    async fn edit(&mut self) {
+        let Some(page) = self.reader.as_ref().map(|reader| reader.page.clone()) else {
+            return;
+        };
+
+        let session = match EditSession::start(&page.id).await {
+            Ok(session) => session,
+            Err(e) => {
+                self.set_reader_status(format!("failed to start editing: {}", e));
+                return self.render_content();
+            }
+        };
+        let left_out = session.left_out;
+
+        let mut status = match run_editor(&session.path) {
+            Ok(true) => {
+                self.set_reader_status("saving...".to_string());
+                self.render().await;
+
+                let outcome = session
+                    .finish()
+                    .await
+                    .unwrap_or_else(|e| format!("save failed: {}", e));
+                if let Some(reader) = self.reader.as_mut() {
+                    reader.fetch().await;
+                    reader.scroll_by(0);
+                }
+                outcome
+            }
+            _ => {
+                session.discard();
+                "editor failed, nothing was saved".to_string()
+            }
+        };
+        if left_out > 0 {
+            status = format!("{} ({} blocks not editable)", status, left_out);
+        }
+        self.set_reader_status(status);
+        self.render().await;
+    }
+
+    async fn run(&mut self, action: Action, command: Command) {
+        match command {
+            Command::None => self.render_content(),
+            Command::Redraw => self.render().await,
+            Command::Open(object) => self.open(object).await,
+            Command::Create(parent, title, template) => self.create(parent, title, template).await,
+            Command::Edit => self.edit().await,
+            Command::Archive(object) => self.archive(object).await,
+            Command::Restore(object) => self.restore(object).await,
+            Command::Check(todo) => self.check(todo).await,
+            Command::Board(database) => self.board(database).await,
+            Command::Calendar(database) => self.calendar(database).await,
+            Command::Refresh => {
+                self.render_content();
+                if let (Section::Calendar, Some(calendar)) = (self.section, self.calendar.as_mut()) {
+                    calendar.fetch().await;
+                }
+                self.render_content();
+            }
+            Command::EditRow => self.edit_row().await,
+            Command::SetProperty(id, column, value) => self.set_property(id, column, value).await,
+            Command::Query(text) => self.query(text).await,
+            Command::Move(page) => {
+                self.search.picking = Some(page);
+                self.search.open().await;
+                self.render().await;
+            }
+            Command::Goto(section) => {
+                self.section = section;
+                self.render_content();
+            }
+            Command::Unhandled if action == Action::Back => {
+                self.section = Section::Home;
+                self.render_content();
+            }
+            Command::Unhandled => {}
+        }
+    }
+
+    // returns false once the user asks to quit
+    pub async fn handle(&mut self, key: KeyEvent) -> bool {
+        // any key closes the help overlay
+        if self.help_open {
+            self.help_open = false;
+            self.render().await;
+            return true;
+        }
+
+        let mode = self.mode();
+        let action = match lookup(&key, mode, self.section) {
+            Some(action) => action,
+            None => return true,
+        };
+
+        if mode == Mode::Search {
+            if self.search.update(action, &key).await {
+                self.render().await;
+                match (self.search.picking.take(), self.search.chosen.take()) {
+                    (Some(page), Some(parent)) => self.move_page(page, parent).await,
+                    (None, Some(object)) => self.open(object).await,
+                    _ => {}
+                }
+            }
+            return true;
+        }
+
+        match action {
+            Action::Quit => return false,
+            Action::Search => {
+                self.search.open().await;
+                self.render().await;
+            }
+            Action::Help => {
+                self.help_open = true;
+                self.render_help();
+            }
+            Action::Login => {
+                if *AUTHENTICATED.lock().unwrap() {
+                    logout();
+                    self.sync_session().await;
+                } else {
+                    self.section = Section::Auth;
+                    self.render_content();
+                }
+            }
+            Action::Home => self.switch(Section::Home).await,
+            Action::Pages => self.switch(Section::Pages).await,
+            Action::Tables => self.switch(Section::Tables).await,
+            Action::Favorites => self.switch(Section::Favorites).await,
+            Action::Recents => self.switch(Section::Recents).await,
+            Action::Settings => self.switch(Section::Settings).await,
+            Action::Trash => self.switch(Section::Trash).await,
+            Action::Todos => self.todos().await,
+            Action::Journal => self.journal().await,
+            _ => {
+                let (view, workspace) = self.view_mut();
+                let command = view.map_or(Command::Unhandled, |view| view.update(action, &key, workspace));
+                self.run(action, command).await;
+            }
+        }
+
+        true
+    }
+}
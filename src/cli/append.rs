@@ -0,0 +1,131 @@
+use std::{
+    error::Error,
+    io::{stdin, Read},
+};
+
+use chrono::Local;
+use serde_json::{json, Value};
+
+use crate::{
+    api::{
+        blocks::{append_block_children, text_to_rich_text},
+        search::{find_exact, is_notion_id, Object},
+    },
+    cli::print_json,
+    database::defaults::{get_default, set_default},
+};
+
+// `- [ ]` and `- [x]` lines become to-dos, everything else a paragraph
+fn line_block(line: &str) -> Value {
+    let trimmed = line.trim_start();
+    let (block_type, checked, text) = if let Some(text) = trimmed.strip_prefix("- [ ]") {
+        ("to_do", Some(false), text.trim_start())
+    } else if let Some(text) = trimmed
+        .strip_prefix("- [x]")
+        .or_else(|| trimmed.strip_prefix("- [X]"))
+    {
+        ("to_do", Some(true), text.trim_start())
+    } else {
+        ("paragraph", None, line.trim_end())
+    };
+
+    let mut content = json!({ "rich_text": text_to_rich_text(text) });
+    if let Some(checked) = checked {
+        content["checked"] = json!(checked);
+    }
+    json!({ "object": "block", "type": block_type, block_type: content })
+}
+
+fn inbox() -> Result<Object, Box<dyn Error>> {
+    get_default("inbox")?
+        .ok_or_else(|| "no inbox page set, run notion_cli inbox <page> or name the page first".into())
+}
+
+// the page named by --to or the first word, or else the inbox. only an id or an exact
+// title will do, so a mistyped name is appended to the inbox as text rather than
+// landing it on some other page. the page's word is taken off the text
+async fn target(words: &mut Vec<String>, to: Option<&str>) -> Result<Object, Box<dyn Error>> {
+    let page = match (to, words.first()) {
+        (Some(reference), _) => find_exact(reference).await?,
+        (None, Some(first)) if is_notion_id(first) => {
+            let page = find_exact(first).await?;
+            words.remove(0);
+            page
+        }
+        (None, Some(first)) if first != "-" => match find_exact(first).await {
+            Ok(page) => {
+                words.remove(0);
+                page
+            }
+            Err(_) => inbox()?,
+        },
+        _ => inbox()?,
+    };
+    if page.object_type != "page" {
+        return Err(format!("\"{}\" is a database, not a page", page.title).into());
+    }
+    Ok(page)
+}
+
+// the words given, or stdin when there are none or just `-`
+pub async fn append(mut words: Vec<String>, to: Option<&str>, timestamp: bool, json: bool) -> Result<(), Box<dyn Error>> {
+    let page = target(&mut words, to).await?;
+    let text = match words.as_slice() {
+        [] => None,
+        [dash] if dash == "-" => None,
+        words => Some(words.join(" ")),
+    };
+    let text = match text {
+        Some(text) => text,
+        None => {
+            let mut input = String::new();
+            stdin().read_to_string(&mut input)?;
+            input
+        }
+    };
+
+    let mut blocks: Vec<Value> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(line_block)
+        .collect();
+    if blocks.is_empty() {
+        return Err("nothing to append".into());
+    }
+    if timestamp {
+        let heading = Local::now().format("%Y-%m-%d %H:%M").to_string();
+        blocks.insert(
+            0,
+            json!({ "object": "block", "type": "heading_3", "heading_3": { "rich_text": text_to_rich_text(&heading) } }),
+        );
+    }
+
+    let count = blocks.len();
+    let ids = append_block_children(&page.id, None, blocks).await?;
+
+    if json {
+        return print_json(&json!({ "page": page, "blocks": ids }));
+    }
+    println!("appended {} blocks to {}", count, page.title);
+    Ok(())
+}
+
+pub async fn set_inbox(reference: Option<String>, json: bool) -> Result<(), Box<dyn Error>> {
+    let page = match reference {
+        Some(reference) => {
+            let page = find_exact(&reference).await?;
+            if page.object_type != "page" {
+                return Err(format!("\"{}\" is a database, not a page", page.title).into());
+            }
+            set_default("inbox", &page);
+            page
+        }
+        None => inbox()?,
+    };
+
+    if json {
+        return print_json(&page);
+    }
+    println!("{}\t{}", page.id, page.title);
+    Ok(())
+}
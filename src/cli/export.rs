@@ -0,0 +1,228 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fs,
+    io::{stdout, Write},
+    path::{Component, Path, PathBuf},
+};
+
+use clap::ValueEnum;
+use serde_json::{Map, Value};
+
+use crate::{
+    api::{
+        blocks::{get_block_children, Block},
+        databases::{get_columns, property_value, query_database, Column, Row},
+        search::{build_tree, find_by_id, find_database, find_object, get_object, search_api, Object},
+    },
+    cli::print_json,
+    utils::markdown::blocks_to_markdown,
+};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    Md,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum TableFormat {
+    Csv,
+    Jsonl,
+}
+
+// titles can contain anything, file names can't
+fn file_name(title: &str, taken: &mut HashSet<String>) -> String {
+    let cleaned: String = title
+        .trim()
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c if c.is_control() => '-',
+            c => c,
+        })
+        .collect();
+    // "." and ".." would point at this directory or its parent
+    let base = if cleaned.chars().all(|c| c == '.') { "Untitled".to_string() } else { cleaned };
+
+    let mut name = base.clone();
+    let mut n = 2;
+    while !taken.insert(name.to_lowercase()) {
+        name = format!("{} ({})", base, n);
+        n += 1;
+    }
+    name
+}
+
+// every part of the path below the export root is a plain name
+fn within(root: &Path, path: &Path) -> bool {
+    path.strip_prefix(root)
+        .is_ok_and(|rest| rest.components().all(|part| matches!(part, Component::Normal(_))))
+}
+
+// child pages can sit inside toggles, columns and the like
+fn child_pages(blocks: &[Block], ids: &mut Vec<String>) {
+    for block in blocks {
+        if block.block_type == "child_page" {
+            ids.push(block.id.clone());
+        }
+        child_pages(&block.children, ids);
+    }
+}
+
+async fn export_page(
+    page: &Object,
+    name: &str,
+    dir: &Path,
+    root: &Path,
+    written: &mut Vec<PathBuf>,
+) -> Result<(), Box<dyn Error>> {
+    let path = dir.join(format!("{}.md", name));
+    let child_dir = dir.join(name);
+    if !within(root, &path) || !within(root, &child_dir) {
+        return Err(format!("\"{}\" would be written outside {}", page.title, root.display()).into());
+    }
+    let blocks = get_block_children(&page.id).await?;
+
+    let mut ids = Vec::new();
+    child_pages(&blocks, &mut ids);
+
+    // subpages go in a directory named after this page, next to its file
+    let mut links = HashMap::new();
+    let mut taken = HashSet::new();
+    for id in ids {
+        let child = get_object(&id).await?;
+        let child_name = file_name(&child.title, &mut taken);
+        links.insert(id, format!("{}/{}.md", name, child_name));
+
+        fs::create_dir_all(&child_dir)?;
+        Box::pin(export_page(&child, &child_name, &child_dir, root, written)).await?;
+    }
+
+    let body = blocks_to_markdown(&blocks, &links);
+    fs::write(&path, format!("# {}\n\n{}\n", page.title, body))?;
+    written.push(path);
+    Ok(())
+}
+
+pub async fn export(
+    reference: &str,
+    format: ExportFormat,
+    out: &Path,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    let page = find_object(reference).await?;
+    if page.object_type != "page" {
+        return Err(format!("\"{}\" is a database, not a page", page.title).into());
+    }
+
+    fs::create_dir_all(out)?;
+    let mut written = Vec::new();
+    match format {
+        ExportFormat::Md => {
+            let name = file_name(&page.title, &mut HashSet::new());
+            export_page(&page, &name, out, out, &mut written).await?;
+        }
+    }
+
+    if json {
+        return print_json(&written);
+    }
+    for path in &written {
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(csv_cell).collect::<Vec<String>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+// the key the row's own id is kept under, apart from any property named "id"
+pub const ROW_ID: &str = "_id";
+
+pub fn check_row_id(columns: &[Column]) -> Result<(), Box<dyn Error>> {
+    if columns.iter().any(|column| column.name == ROW_ID) {
+        return Err(format!("a property named \"{}\" would hide the row ids, rename it first", ROW_ID).into());
+    }
+    Ok(())
+}
+
+// a row as the id and a typed value per column, relations as titles when given the tree
+pub fn row_record(row: &Row, columns: &[Column], tree: Option<&[Object]>) -> Map<String, Value> {
+    let mut record = Map::new();
+    record.insert(ROW_ID.to_string(), Value::String(row.id.clone()));
+    for column in columns {
+        let property = &row.properties[&column.name];
+        let mut value = property_value(property);
+        if let (Some(tree), "relation", Value::Array(ids)) = (tree, column.property_type.as_str(), &mut value) {
+            for id in ids.iter_mut() {
+                if let Some(page) = id.as_str().and_then(|id| find_by_id(tree, id)) {
+                    *id = Value::String(page.title.clone());
+                }
+            }
+        }
+        record.insert(column.name.clone(), value);
+    }
+    record
+}
+
+pub async fn export_db(
+    reference: &str,
+    format: TableFormat,
+    out: Option<&Path>,
+    titles: bool,
+) -> Result<(), Box<dyn Error>> {
+    let database = find_database(reference).await?;
+    let columns = get_columns(&database.id).await?;
+    check_row_id(&columns)?;
+    let rows = query_database(&database.id).await?;
+
+    // relation ids are only resolved when asked, it needs the whole workspace
+    let tree = if titles {
+        build_tree(&search_api(None).await?)
+    } else {
+        Vec::new()
+    };
+
+    let records: Vec<Map<String, Value>> = rows
+        .iter()
+        .map(|row| row_record(row, &columns, titles.then_some(tree.as_slice())))
+        .collect();
+
+    let output: Box<dyn Write> = match out {
+        Some(path) => Box::new(fs::File::create(path)?),
+        None => Box::new(stdout()),
+    };
+
+    match format {
+        TableFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(output);
+            let header = std::iter::once(ROW_ID).chain(columns.iter().map(|column| column.name.as_str()));
+            writer.write_record(header)?;
+            for record in &records {
+                let cells = std::iter::once(&record[ROW_ID])
+                    .chain(columns.iter().map(|column| &record[&column.name]))
+                    .map(csv_cell);
+                writer.write_record(cells)?;
+            }
+            writer.flush()?;
+        }
+        TableFormat::Jsonl => {
+            let mut output = output;
+            for record in &records {
+                writeln!(output, "{}", serde_json::to_string(record)?)?;
+            }
+            output.flush()?;
+        }
+    }
+
+    if let Some(path) = out {
+        eprintln!("{} rows written to {}", records.len(), path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,239 @@
+use std::{
+    collections::HashSet,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use serde_json::{json, Map};
+
+use crate::{
+    api::{
+        blocks::append_block_children,
+        databases::{coerce_property, create_row, get_columns, is_writable, Column},
+        pages::create_page,
+        search::{find_database, find_object, Object},
+        users::get_people,
+    },
+    cli::print_json,
+    utils::markdown::markdown_to_blocks,
+};
+
+#[derive(Serialize)]
+struct Imported {
+    path: PathBuf,
+    id: String,
+    title: String,
+    url: String,
+}
+
+fn title_from(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Untitled".to_string())
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("md") || extension.eq_ignore_ascii_case("markdown"))
+}
+
+async fn import_file(
+    path: &Path,
+    parent: &Object,
+    imported: &mut Vec<Imported>,
+) -> Result<(), Box<dyn Error>> {
+    let markdown = fs::read_to_string(path)?;
+    let (title, blocks) = markdown_to_blocks(&markdown);
+    let title = title.unwrap_or_else(|| title_from(path));
+
+    let page = create_page(&parent.id, &title).await?;
+    append_block_children(&page.id, None, blocks).await?;
+    imported.push(Imported {
+        path: path.to_path_buf(),
+        id: page.id.clone(),
+        title,
+        url: page.url(),
+    });
+
+    // export writes subpages into a directory named like the file, next to it
+    let companion = path.with_extension("");
+    if companion.is_dir() {
+        import_entries(&companion, &page, imported).await?;
+    }
+    Ok(())
+}
+
+async fn import_dir(
+    path: &Path,
+    parent: &Object,
+    imported: &mut Vec<Imported>,
+) -> Result<(), Box<dyn Error>> {
+    let title = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Untitled".to_string());
+
+    let page = create_page(&parent.id, &title).await?;
+    imported.push(Imported {
+        path: path.to_path_buf(),
+        id: page.id.clone(),
+        title,
+        url: page.url(),
+    });
+    import_entries(path, &page, imported).await
+}
+
+async fn import_entries(
+    dir: &Path,
+    parent: &Object,
+    imported: &mut Vec<Imported>,
+) -> Result<(), Box<dyn Error>> {
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect();
+    entries.sort();
+
+    for entry in &entries {
+        if entry.is_file() && is_markdown(entry) {
+            Box::pin(import_file(entry, parent, imported)).await?;
+        } else if entry.is_dir() && !entries.iter().any(|file| is_markdown(file) && file.with_extension("") == *entry) {
+            // directories paired with a markdown file were imported along with it
+            Box::pin(import_dir(entry, parent, imported)).await?;
+        }
+    }
+    Ok(())
+}
+
+pub async fn import(path: &Path, reference: &str, json: bool) -> Result<(), Box<dyn Error>> {
+    let parent = find_object(reference).await?;
+    if parent.object_type != "page" {
+        return Err(format!("\"{}\" is a database, not a page", parent.title).into());
+    }
+
+    let mut imported = Vec::new();
+    if path.is_dir() {
+        import_dir(path, &parent, &mut imported).await?;
+    } else if is_markdown(path) {
+        import_file(path, &parent, &mut imported).await?;
+    } else {
+        return Err(format!("{} is not a markdown file or directory", path.display()).into());
+    }
+
+    if json {
+        return print_json(&imported);
+    }
+    for page in &imported {
+        println!("{}\t{}", page.url, page.path.display());
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct RowError {
+    row: usize,
+    column: String,
+    error: String,
+}
+
+// match csv headers to properties, exact names first and then ignoring case
+fn map_columns(headers: &csv::StringRecord, columns: &[Column]) -> Vec<Option<Column>> {
+    headers
+        .iter()
+        .map(|header| {
+            let column = columns
+                .iter()
+                .find(|column| column.name == header)
+                .or_else(|| columns.iter().find(|column| column.name.eq_ignore_ascii_case(header)));
+            match column {
+                Some(column) if is_writable(&column.property_type) => Some(column.clone()),
+                Some(column) => {
+                    eprintln!(
+                        "skipping column \"{}\", {} properties are computed by notion",
+                        header, column.property_type
+                    );
+                    None
+                }
+                None => {
+                    eprintln!("skipping column \"{}\", the database has no such property", header);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+pub async fn import_csv(
+    path: &Path,
+    reference: &str,
+    dry_run: bool,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    let database = find_database(reference).await?;
+    let columns = get_columns(&database.id).await?;
+
+    let mut reader = csv::Reader::from_path(path)?;
+    let mapping = map_columns(reader.headers()?, &columns);
+
+    // people cells can name members by email, which needs the member list
+    let people = if mapping.iter().flatten().any(|column| column.property_type == "people") {
+        get_people().await?
+    } else {
+        Vec::new()
+    };
+
+    let mut rows = Vec::new();
+    let mut errors = Vec::<RowError>::new();
+    for (i, record) in reader.records().enumerate() {
+        let record = record?;
+        let mut properties = Map::new();
+        for (cell, column) in record.iter().zip(&mapping) {
+            let Some(column) = column else { continue };
+            match coerce_property(column, cell, &people) {
+                Ok(Some(value)) => {
+                    properties.insert(column.name.clone(), value);
+                }
+                Ok(None) => {}
+                Err(error) => errors.push(RowError {
+                    row: i + 1,
+                    column: column.name.clone(),
+                    error,
+                }),
+            }
+        }
+        rows.push(properties);
+    }
+
+    if dry_run && json {
+        return print_json(&json!({ "rows": rows.len(), "errors": errors }));
+    }
+    for error in &errors {
+        eprintln!("row {}, column \"{}\": {}", error.row, error.column, error.error);
+    }
+    // all rows are checked before any is written, so a bad file leaves the database untouched
+    if !errors.is_empty() {
+        let failed: HashSet<usize> = errors.iter().map(|error| error.row).collect();
+        return Err(format!("{} of {} rows have type errors, nothing was written", failed.len(), rows.len()).into());
+    }
+    if dry_run {
+        println!("{} rows ready to import into {}", rows.len(), database.title);
+        return Ok(());
+    }
+
+    let mut created = Vec::new();
+    for (i, properties) in rows.into_iter().enumerate() {
+        let id = create_row(&database.id, properties.into())
+            .await
+            .map_err(|e| format!("row {}: {} ({} rows were created before it)", i + 1, e, created.len()))?;
+        if !json {
+            println!("{}", id);
+        }
+        created.push(json!({ "row": i + 1, "id": id }));
+    }
+
+    if json {
+        return print_json(&created);
+    }
+    Ok(())
+}
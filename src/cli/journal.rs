@@ -0,0 +1,64 @@
+use std::error::Error;
+
+use chrono::{Local, NaiveDate};
+use serde_json::json;
+
+use crate::{
+    api::{
+        journal::{journal, journal_entry},
+        search::find_object,
+    },
+    cli::print_json,
+    database::defaults::set_default,
+    utils::edit::{launch_editor, EditSession},
+};
+
+pub async fn set_journal(reference: Option<String>, json: bool) -> Result<(), Box<dyn Error>> {
+    let parent = match reference {
+        Some(reference) => {
+            let parent = find_object(&reference).await?;
+            set_default("journal", &parent);
+            parent
+        }
+        None => journal()?,
+    };
+
+    if json {
+        return print_json(&parent);
+    }
+    println!("{}\t{}\t{}", parent.id, parent.object_type, parent.title);
+    Ok(())
+}
+
+pub async fn open_entry(date: Option<NaiveDate>, edit: bool, json: bool) -> Result<(), Box<dyn Error>> {
+    let date = date.unwrap_or_else(|| Local::now().date_naive());
+    let (entry, created) = journal_entry(date).await?;
+
+    let mut outcome = None;
+    if edit {
+        let session = EditSession::start(&entry.id).await?;
+        if !launch_editor(&session.path)? {
+            session.discard();
+            return Err("editor failed, nothing was saved".into());
+        }
+        outcome = Some(session.finish().await?);
+    }
+
+    if json {
+        return print_json(&json!({
+            "id": entry.id,
+            "title": entry.title,
+            "url": entry.url(),
+            "created": created,
+            "edit": outcome,
+        }));
+    }
+    if created {
+        eprintln!("created {}", entry.title);
+    }
+    if let Some(outcome) = outcome {
+        eprintln!("{}", outcome);
+    }
+    println!("{}", entry.url());
+    Ok(())
+}
@@ -0,0 +1,253 @@
+pub mod append;
+pub mod export;
+pub mod import;
+pub mod journal;
+pub mod organize;
+pub mod query;
+pub mod read;
+pub mod templates;
+pub mod todos;
+
+use std::{error::Error, path::PathBuf};
+
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+
+use crate::database::user::get_access_token;
+
+use export::{ExportFormat, TableFormat};
+use templates::TemplateCommand;
+
+// without a subcommand the binary opens the terminal ui
+#[derive(Parser)]
+#[command(name = "notion_cli", version, about = "Browse and script your Notion workspace")]
+pub struct Cli {
+    /// Print machine readable JSON instead of text
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Search pages and databases by title
+    Search { query: String },
+    /// List the pages and databases shared with the integration
+    Ls {
+        /// Nest children under their parents
+        #[arg(long)]
+        tree: bool,
+    },
+    /// Print a page's content
+    Cat {
+        /// Page id or title
+        page: String,
+    },
+    /// Open a page or database in the browser
+    Open { title: String },
+    /// Export a page and its subpages to files
+    Export {
+        /// Page id or title
+        page: String,
+        #[arg(long, value_enum, default_value = "md")]
+        format: ExportFormat,
+        /// Directory to write into
+        #[arg(long, default_value = ".")]
+        out: PathBuf,
+    },
+    /// Export every row of a database
+    ExportDb {
+        /// Database id or title
+        database: String,
+        #[arg(long, value_enum, default_value = "csv")]
+        format: TableFormat,
+        /// File to write to instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Write relations as page titles instead of ids
+        #[arg(long)]
+        titles: bool,
+    },
+    /// Create pages from a markdown file or a directory of them
+    Import {
+        /// Markdown file or directory
+        path: PathBuf,
+        /// Page id or title to create the pages under
+        #[arg(long)]
+        parent: String,
+    },
+    /// Append lines to a page, or to the inbox
+    ///
+    /// `append <page> [text...]` writes to the page when the first word is its id
+    /// or exact title, otherwise every word is text for the inbox. Lines starting
+    /// with `- [ ]` become to-dos. Without text, or with `-`, the lines are read
+    /// from stdin.
+    Append {
+        /// Page id or exact title, then the text to append
+        text: Vec<String>,
+        /// Page id or exact title, for when the text could be taken for a page
+        #[arg(long)]
+        to: Option<String>,
+        /// Put a heading with the current time above the lines
+        #[arg(long)]
+        timestamp: bool,
+    },
+    /// Show or set the page `append` writes to by default
+    Inbox {
+        /// Page id or title
+        page: Option<String>,
+    },
+    /// Add the rows of a CSV file to a database
+    ImportDb {
+        /// CSV file with a header row naming the properties
+        file: PathBuf,
+        /// Database id or title
+        #[arg(long)]
+        database: String,
+        /// Check every row against the schema without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Find or create the journal entry for a day
+    ///
+    /// Entries are child pages of the journal page, or rows of the journal database
+    /// dated that day. New entries are filled from the "journal" template, or from
+    /// journal.md next to the config file.
+    Journal {
+        /// Day of the entry as YYYY-MM-DD, today when left out
+        #[arg(long)]
+        date: Option<NaiveDate>,
+        /// Open the entry in $EDITOR and save the changes back
+        #[arg(long)]
+        edit: bool,
+        /// Page or database id or title to keep the journal in
+        #[arg(long, conflicts_with_all = ["date", "edit"])]
+        set: Option<String>,
+        /// Show the journal page or database instead of an entry
+        #[arg(long, conflicts_with_all = ["date", "edit", "set"])]
+        show: bool,
+    },
+    /// Create a page under a page, or a row in a database
+    New {
+        title: String,
+        /// Page or database id or title to create it in
+        #[arg(long)]
+        parent: String,
+        /// Saved template to fill the new page from
+        #[arg(long)]
+        template: Option<String>,
+    },
+    /// Move a page, database or row to the trash
+    Archive {
+        /// Page or database id or exact title
+        page: String,
+        /// Don't ask for confirmation
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Restore an archived page, or list the ones archived with notion_cli
+    Restore {
+        /// Page or database id, or the title of one archived with notion_cli
+        page: Option<String>,
+    },
+    /// Move a page under another page or into a database
+    Move {
+        /// Page id or exact title
+        page: String,
+        /// Page or database id or exact title to move it to
+        #[arg(long)]
+        to: String,
+    },
+    /// List the open to-dos collected from the todo sources
+    ///
+    /// Pages contribute their unchecked to-do blocks, databases the rows whose
+    /// status is not in the complete group, or without a status, whose checkbox
+    /// is clear.
+    Todos {
+        /// Page or database id or title to collect todos from
+        #[arg(long)]
+        add: Option<String>,
+        /// Stop collecting todos from a source
+        #[arg(long, conflicts_with = "add")]
+        remove: Option<String>,
+        /// List the sources instead of the todos
+        #[arg(long, conflicts_with_all = ["add", "remove"])]
+        sources: bool,
+        /// Check off the to-do block or row with this id
+        #[arg(long, conflicts_with_all = ["add", "remove", "sources"])]
+        check: Option<String>,
+    },
+    /// List the rows of a database matching a query
+    ///
+    /// Conditions look like `status = "Doing"`, `due < today`, `tags ~ work` or
+    /// `notes is not empty`, joined with and / or and grouped with parentheses.
+    /// `sort:due` orders by a property, `sort:-due` in reverse.
+    Query {
+        /// Database id or title, or @name for a saved query
+        database: String,
+        /// The query, quoted as one argument or spread over several
+        query: Vec<String>,
+        /// Save the query under a name to run later as @name
+        #[arg(long)]
+        save: Option<String>,
+    },
+    /// List saved queries
+    Queries {
+        /// Delete the saved query with this name instead
+        #[arg(long)]
+        delete: Option<String>,
+    },
+    /// Manage the templates new pages can be created from
+    Template {
+        #[command(subcommand)]
+        command: TemplateCommand,
+    },
+}
+
+pub async fn run(command: Command, json: bool) -> Result<(), Box<dyn Error>> {
+    if get_access_token().is_empty() {
+        return Err("not logged in, run notion_cli and press [l] to login".into());
+    }
+
+    match command {
+        Command::Search { query } => read::search(&query, json).await,
+        Command::Ls { tree } => read::ls(tree, json).await,
+        Command::Cat { page } => read::cat(&page, json).await,
+        Command::Open { title } => read::open(&title, json).await,
+        Command::Export { page, format, out } => export::export(&page, format, &out, json).await,
+        Command::ExportDb { database, format, out, titles } => {
+            export::export_db(&database, format, out.as_deref(), titles).await
+        }
+        Command::Import { path, parent } => import::import(&path, &parent, json).await,
+        Command::Append { text, to, timestamp } => append::append(text, to.as_deref(), timestamp, json).await,
+        Command::Inbox { page } => append::set_inbox(page, json).await,
+        Command::ImportDb { file, database, dry_run } => {
+            import::import_csv(&file, &database, dry_run, json).await
+        }
+        Command::Journal { set: Some(reference), .. } => journal::set_journal(Some(reference), json).await,
+        Command::Journal { show: true, .. } => journal::set_journal(None, json).await,
+        Command::Journal { date, edit, .. } => journal::open_entry(date, edit, json).await,
+        Command::New { title, parent, template } => {
+            templates::new(&title, &parent, template.as_deref(), json).await
+        }
+        Command::Template { command } => templates::template(command, json).await,
+        Command::Query { database, query, save } => query::query(&database, query, save, json).await,
+        Command::Queries { delete } => query::queries(delete, json),
+        Command::Todos { add: Some(reference), .. } => todos::add_source(&reference, json).await,
+        Command::Todos { remove: Some(reference), .. } => todos::remove_source(&reference),
+        Command::Todos { sources: true, .. } => todos::sources(json),
+        Command::Todos { check: Some(id), .. } => todos::check(&id, json).await,
+        Command::Todos { .. } => todos::todos(json).await,
+        Command::Archive { page, yes } => organize::archive(&page, yes, json).await,
+        Command::Restore { page } => organize::restore(page, json).await,
+        Command::Move { page, to } => organize::move_to(&page, &to, json).await,
+    }
+}
+
+pub fn print_json<T: Serialize>(value: &T) -> Result<(), Box<dyn Error>> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use std::{
+    error::Error,
+    io::{stdin, stdout, IsTerminal, Write},
+};
+
+use serde_json::json;
+
+use crate::{
+    api::{
+        pages::{move_page, set_archived},
+        search::{find_exact, get_object, is_notion_id},
+    },
+    cli::print_json,
+    database::archived::{get_archived, record_archived, remove_archived},
+};
+
+// ask before a destructive change unless --yes was given
+fn confirm(question: &str, yes: bool) -> Result<bool, Box<dyn Error>> {
+    if yes {
+        return Ok(true);
+    }
+    if !stdin().is_terminal() {
+        return Err("refusing to continue without a terminal to confirm in, pass --yes".into());
+    }
+
+    print!("{} [y/N] ", question);
+    stdout().flush()?;
+    let mut answer = String::new();
+    stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+pub async fn archive(reference: &str, yes: bool, json: bool) -> Result<(), Box<dyn Error>> {
+    let object = find_exact(reference).await?;
+    if !confirm(&format!("archive {} \"{}\"?", object.object_type, object.title), yes)? {
+        return Err("nothing was archived".into());
+    }
+
+    set_archived(&object, true).await?;
+    record_archived(&object);
+
+    if json {
+        return print_json(&object);
+    }
+    println!("archived {}, undo with notion_cli restore {}", object.title, object.id);
+    Ok(())
+}
+
+// without a reference, list what was archived from here
+pub async fn restore(reference: Option<String>, json: bool) -> Result<(), Box<dyn Error>> {
+    let archived = get_archived()?;
+    let Some(reference) = reference else {
+        if json {
+            return print_json(&archived);
+        }
+        for item in &archived {
+            println!("{}\t{}\t{}", item.id, item.object_type, item.title);
+        }
+        return Ok(());
+    };
+
+    // titles can only be matched locally, archived pages don't show up in search
+    let object = match archived
+        .iter()
+        .find(|item| item.id.replace('-', "") == reference.replace('-', "") || item.title == reference)
+    {
+        Some(item) => item.to_object(),
+        None if is_notion_id(&reference) => get_object(&reference).await?,
+        None => return Err(format!("nothing archived matches \"{}\"", reference).into()),
+    };
+
+    set_archived(&object, false).await?;
+    remove_archived(&object.id);
+
+    if json {
+        return print_json(&object);
+    }
+    println!("restored {}", object.title);
+    Ok(())
+}
+
+pub async fn move_to(reference: &str, to: &str, json: bool) -> Result<(), Box<dyn Error>> {
+    let page = find_exact(reference).await?;
+    if page.object_type != "page" {
+        return Err(format!("\"{}\" is a database, only pages can be moved", page.title).into());
+    }
+    let parent = find_exact(to).await?;
+    if parent.id == page.id {
+        return Err("a page can't be moved into itself".into());
+    }
+
+    move_page(&page.id, &parent).await?;
+
+    if json {
+        return print_json(&json!({ "id": page.id, "title": page.title, "parent": parent }));
+    }
+    println!("moved {} to {}", page.title, parent.title);
+    Ok(())
+}
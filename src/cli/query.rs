@@ -0,0 +1,92 @@
+use std::error::Error;
+
+use crate::{
+    api::{
+        databases::{get_columns, property_text, query_database_filtered},
+        search::find_database,
+    },
+    cli::{
+        export::{check_row_id, row_record, ROW_ID},
+        print_json,
+    },
+    database::queries::{delete_query, get_queries, get_query, save_query, SavedQuery},
+    utils::query::{narrow, parse_query},
+};
+
+// `query <database> <expression...>`, or `query @name` to run a saved one
+pub async fn query(
+    database: &str,
+    words: Vec<String>,
+    save: Option<String>,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    let (database, text) = match database.strip_prefix('@') {
+        Some(name) => {
+            let saved = get_query(name)?.ok_or_else(|| format!("no saved query named \"{}\"", name))?;
+            // words after a saved query narrow it further
+            let text = if words.is_empty() {
+                saved.query
+            } else {
+                narrow(&saved.query, &words.join(" "))
+            };
+            (find_database(&saved.database_id).await?, text)
+        }
+        None => (find_database(database).await?, words.join(" ")),
+    };
+
+    let columns = get_columns(&database.id).await?;
+    check_row_id(&columns)?;
+    let parsed = parse_query(&text, &columns)?;
+    let rows = query_database_filtered(&database.id, parsed.filter, parsed.sorts).await?;
+
+    if let Some(name) = save {
+        let name = name.trim_start_matches('@').to_string();
+        let saved = save_query(&SavedQuery {
+            name: name.clone(),
+            database_id: database.id.clone(),
+            database_title: database.title.clone(),
+            query: text,
+        });
+        if !saved {
+            let owner = get_query(&name)?.map(|saved| saved.database_title).unwrap_or_default();
+            return Err(
+                format!("@{} is already a query for {}, pick another name or delete it first", name, owner).into(),
+            );
+        }
+        eprintln!("saved as @{}", name);
+    }
+
+    if json {
+        let records: Vec<_> = rows.iter().map(|row| row_record(row, &columns, None)).collect();
+        return print_json(&records);
+    }
+    let header: Vec<&str> = std::iter::once(ROW_ID).chain(columns.iter().map(|column| column.name.as_str())).collect();
+    println!("{}", header.join("\t"));
+    for row in &rows {
+        let cells: Vec<String> = std::iter::once(row.id.clone())
+            .chain(columns.iter().map(|column| property_text(&row.properties[&column.name])))
+            .collect();
+        println!("{}", cells.join("\t"));
+    }
+    Ok(())
+}
+
+pub fn queries(delete: Option<String>, json: bool) -> Result<(), Box<dyn Error>> {
+    if let Some(name) = delete {
+        let name = name.trim_start_matches('@');
+        if !delete_query(name) {
+            return Err(format!("no saved query named \"{}\"", name).into());
+        }
+        println!("deleted @{}", name);
+        return Ok(());
+    }
+
+    let queries = get_queries(None)?;
+    if json {
+        return print_json(&queries);
+    }
+    for saved in &queries {
+        println!("@{}\t{}\t{}", saved.name, saved.database_title, saved.query);
+    }
+    Ok(())
+}
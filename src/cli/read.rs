@@ -0,0 +1,116 @@
+use std::{
+    error::Error,
+    io::{stdout, IsTerminal},
+};
+
+use crossterm::terminal::size;
+use serde_json::json;
+
+use crate::{
+    api::{
+        blocks::get_block_children,
+        search::{build_tree, find_object, flatten_tree, search_api, Object},
+    },
+    cli::print_json,
+    utils::search::match_search,
+    views::reader::render_blocks,
+};
+
+// wrap to the terminal when there is one, otherwise to a fixed width for pipes
+fn text_width() -> usize {
+    if stdout().is_terminal() {
+        size().map(|(cols, _)| cols as usize).unwrap_or(80)
+    } else {
+        80
+    }
+}
+
+fn print_object(object: &Object, depth: usize) {
+    println!(
+        "{}\t{}\t{}{}",
+        object.id,
+        object.object_type,
+        "  ".repeat(depth),
+        object.title
+    );
+}
+
+fn print_tree(tree: &[Object], depth: usize) {
+    for object in tree {
+        print_object(object, depth);
+        print_tree(&object.children, depth + 1);
+    }
+}
+
+pub async fn search(query: &str, json: bool) -> Result<(), Box<dyn Error>> {
+    let results = search_api(Some(query.to_string())).await;
+    let matches = match_search(query, &results);
+    results?;
+
+    if json {
+        return print_json(&matches.values().collect::<Vec<&Object>>());
+    }
+    for object in matches.values() {
+        print_object(object, 0);
+    }
+    Ok(())
+}
+
+pub async fn ls(tree: bool, json: bool) -> Result<(), Box<dyn Error>> {
+    let objects = build_tree(&search_api(None).await?);
+
+    if tree {
+        return if json {
+            print_json(&objects)
+        } else {
+            print_tree(&objects, 0);
+            Ok(())
+        };
+    }
+
+    let flat: Vec<Object> = flatten_tree(&objects)
+        .into_iter()
+        .map(|object| Object {
+            children: Vec::new(),
+            ..object
+        })
+        .collect();
+    if json {
+        return print_json(&flat);
+    }
+    for object in &flat {
+        print_object(object, 0);
+    }
+    Ok(())
+}
+
+pub async fn cat(reference: &str, json: bool) -> Result<(), Box<dyn Error>> {
+    let page = find_object(reference).await?;
+    if page.object_type != "page" {
+        return Err(format!("\"{}\" is a database, not a page", page.title).into());
+    }
+    let blocks = get_block_children(&page.id).await?;
+
+    if json {
+        return print_json(&json!({ "page": page, "blocks": blocks }));
+    }
+
+    println!("{}\n", page.title);
+    for line in render_blocks(&blocks, text_width()) {
+        let text: String = line.iter().map(|span| span.content().as_str()).collect();
+        println!("{}", text.trim_end());
+    }
+    Ok(())
+}
+
+pub async fn open(title: &str, json: bool) -> Result<(), Box<dyn Error>> {
+    let object = find_object(title).await?;
+    let url = object.url();
+    webbrowser::open(&url)?;
+
+    if json {
+        return print_json(&json!({ "id": object.id, "title": object.title, "url": url }));
+    }
+    println!("{}", url);
+    Ok(())
+}
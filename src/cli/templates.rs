@@ -0,0 +1,113 @@
+use std::{
+    error::Error,
+    fs,
+    io::{stdin, Read},
+    path::Path,
+};
+
+use clap::Subcommand;
+use serde_json::json;
+
+use crate::{
+    api::{
+        search::find_object,
+        templates::{capture_page, create_from_template, find_template},
+    },
+    cli::print_json,
+    database::templates::{delete_template, get_templates, save_template},
+};
+
+#[derive(Subcommand)]
+pub enum TemplateCommand {
+    /// List the saved templates
+    List,
+    /// Print a template's markdown
+    Show { name: String },
+    /// Save a template from a page or a markdown file, replacing any with the same name
+    Save {
+        name: String,
+        /// Page id or title to copy the content of
+        #[arg(long, conflicts_with = "file", required_unless_present = "file")]
+        page: Option<String>,
+        /// Markdown file, or `-` for stdin. {{date}} and {{title}} are filled in on use
+        #[arg(long)]
+        file: Option<String>,
+    },
+    /// Delete a template
+    Delete { name: String },
+}
+
+pub async fn template(command: TemplateCommand, json: bool) -> Result<(), Box<dyn Error>> {
+    match command {
+        TemplateCommand::List => {
+            let templates = get_templates()?;
+            if json {
+                return print_json(&templates);
+            }
+            for template in &templates {
+                let lines = template.body.lines().filter(|line| !line.trim().is_empty()).count();
+                println!("{}\t{} lines", template.name, lines);
+            }
+        }
+        TemplateCommand::Show { name } => {
+            let template = find_template(&name)?;
+            if json {
+                return print_json(&template);
+            }
+            print!("{}", template.body);
+        }
+        TemplateCommand::Save { name, page, file } => {
+            let body = match (page, file.as_deref()) {
+                (Some(reference), _) => {
+                    let page = find_object(&reference).await?;
+                    if page.object_type != "page" {
+                        return Err(format!("\"{}\" is a database, not a page", page.title).into());
+                    }
+                    capture_page(&page.id).await?
+                }
+                (None, Some("-")) => {
+                    let mut input = String::new();
+                    stdin().read_to_string(&mut input)?;
+                    input
+                }
+                (None, Some(path)) => fs::read_to_string(Path::new(path))?,
+                (None, None) => return Err("give a --page or a --file to save from".into()),
+            };
+            save_template(&name, &body);
+
+            if json {
+                return print_json(&json!({ "name": name, "body": body }));
+            }
+            println!("saved template {}", name);
+        }
+        TemplateCommand::Delete { name } => {
+            if !delete_template(&name) {
+                return Err(format!("no template named \"{}\"", name).into());
+            }
+            if json {
+                return print_json(&json!({ "name": name, "deleted": true }));
+            }
+            println!("deleted template {}", name);
+        }
+    }
+    Ok(())
+}
+
+// `new <title> --parent <page|database> [--template <name>]`
+pub async fn new(
+    title: &str,
+    reference: &str,
+    template: Option<&str>,
+    json: bool,
+) -> Result<(), Box<dyn Error>> {
+    // an unknown template fails before anything is created
+    let template = template.map(find_template).transpose()?;
+    let parent = find_object(reference).await?;
+    let page = create_from_template(&parent, title, template.as_ref()).await?;
+
+    if json {
+        return print_json(&json!({ "id": page.id, "title": page.title, "url": page.url() }));
+    }
+    println!("{}", page.url());
+    Ok(())
+}
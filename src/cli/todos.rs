@@ -0,0 +1,87 @@
+use std::error::Error;
+
+use crate::{
+    api::{
+        search::find_object,
+        todos::{check_off, collect_todos},
+    },
+    cli::print_json,
+    database::todo_sources::{add_todo_source, get_todo_sources, remove_todo_source},
+};
+
+pub async fn add_source(reference: &str, json: bool) -> Result<(), Box<dyn Error>> {
+    let object = find_object(reference).await?;
+    add_todo_source(&object);
+
+    if json {
+        return print_json(&object);
+    }
+    println!("collecting todos from {}", object.title);
+    Ok(())
+}
+
+// sources are matched by id or by the title they were added with
+pub fn remove_source(reference: &str) -> Result<(), Box<dyn Error>> {
+    let source = get_todo_sources()?
+        .into_iter()
+        .find(|source| source.id.replace('-', "") == reference.replace('-', "") || source.title == reference)
+        .ok_or_else(|| format!("\"{}\" is not a todo source", reference))?;
+    remove_todo_source(&source.id);
+    println!("no longer collecting todos from {}", source.title);
+    Ok(())
+}
+
+pub fn sources(json: bool) -> Result<(), Box<dyn Error>> {
+    let sources = get_todo_sources()?;
+    if json {
+        return print_json(&sources);
+    }
+    for source in &sources {
+        println!("{}\t{}\t{}", source.id, source.object_type, source.title);
+    }
+    Ok(())
+}
+
+// the open to-dos grouped by the page or database they are in
+pub async fn todos(json: bool) -> Result<(), Box<dyn Error>> {
+    let sources = get_todo_sources()?;
+    if sources.is_empty() {
+        return Err("no todo sources yet, add pages or databases with notion_cli todos --add <page>".into());
+    }
+
+    let (todos, errors) = collect_todos(&sources).await;
+    for error in &errors {
+        eprintln!("{}", error);
+    }
+    if json {
+        return print_json(&todos);
+    }
+
+    for source in &sources {
+        let group: Vec<_> = todos.iter().filter(|todo| todo.source.id == source.id).collect();
+        if group.is_empty() {
+            continue;
+        }
+        println!("{}", source.title);
+        for todo in group {
+            println!("  [ ] {}\t{}", todo.text, todo.id);
+        }
+    }
+    Ok(())
+}
+
+// the id can be any to-do currently listed, block or row
+pub async fn check(id: &str, json: bool) -> Result<(), Box<dyn Error>> {
+    let (todos, _) = collect_todos(&get_todo_sources()?).await;
+    let todo = todos
+        .iter()
+        .find(|todo| todo.id.replace('-', "") == id.replace('-', ""))
+        .ok_or_else(|| format!("no open to-do with id {}", id))?;
+
+    check_off(todo).await?;
+    if json {
+        return print_json(todo);
+    }
+    println!("checked off {}", todo.text);
+    Ok(())
+}
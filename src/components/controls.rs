@@ -8,9 +8,8 @@ use crossterm::{
 };
 
 use crate::{
-    api::auth,
+    app::AUTHENTICATED,
     utils::{controls::write_ctrl, dimentions::get_dimensions},
-    AUTHENTICATED,
 };
 
 pub fn controls(search_enabled: bool, clear: bool) {
@@ -39,7 +38,7 @@ pub fn controls(search_enabled: bool, clear: bool) {
 
     let mut left_ctrl_space = 0;
     for ctrl in left_ctrl {
-        let x_ctrl = x_center - (content_width / 2) + left_ctrl_space as u16;
+        let x_ctrl = x_center - (content_width / 2) + left_ctrl_space;
         let y_ctrl = y_center + (content_height / 2) - 1;
         write_ctrl(ctrl, x_ctrl, y_ctrl);
 
@@ -53,7 +52,7 @@ pub fn controls(search_enabled: bool, clear: bool) {
             " [l]ogin"
         };
 
-        let mut right_ctrl = vec![auth_button, "[h]elp", "[q]uit"];
+        let mut right_ctrl = [auth_button, "[h]elp", "[q]uit"];
         right_ctrl.reverse();
 
         let mut right_ctrl_space = 0;
@@ -61,7 +60,7 @@ pub fn controls(search_enabled: bool, clear: bool) {
             right_ctrl_space += ctrl.len() as u16;
             right_ctrl_space += if i != 0 { 1 } else { 0 };
 
-            let x_ctrl = x_center + (content_width / 2) - right_ctrl_space as u16;
+            let x_ctrl = x_center + (content_width / 2) - right_ctrl_space;
             let y_ctrl = y_center + (content_height / 2) - 1;
             write_ctrl(ctrl, x_ctrl, y_ctrl);
         }
@@ -1,13 +1,131 @@
-use std::{collections::HashMap, io::stdout};
+use std::io::stdout;
 
-use crossterm::{cursor::MoveTo, execute, style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor, Stylize}};
+use crossterm::{cursor::MoveTo, event::{KeyCode, KeyEvent}, execute, style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor, Stylize}};
+use indexmap::IndexMap;
 
-use crate::{api::{pages::get_pages, search::Object}, utils::{controls::write_ctrl, dimentions::get_dimensions}};
+use crate::{
+    api::search::{Object, SearchPaginator, WORKSPACE_CAP},
+    utils::{
+        controls::write_ctrl,
+        dimentions::get_dimensions,
+        keymap::Action,
+        search::match_search,
+        theme::accent_color,
+    },
+};
+
+pub struct Search {
+    pub enabled: bool,
+    input: String,
+    items: IndexMap<String, Object>,
+    pos: usize,
+    pages: SearchPaginator,
+    // page waiting for a new parent to be picked from the results
+    pub picking: Option<Object>,
+    // the result enter was pressed on, taken by the app once search closes
+    pub chosen: Option<Object>,
+}
+
+impl Search {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            input: String::new(),
+            items: IndexMap::new(),
+            pos: 0,
+            pages: SearchPaginator::new(None, WORKSPACE_CAP),
+            picking: None,
+            chosen: None,
+        }
+    }
+
+    // restart the search from the first page of results for the current input
+    async fn refresh(&mut self) {
+        self.pages = SearchPaginator::new(Some(self.input.clone()), WORKSPACE_CAP);
+        let results = self.pages.next_page().await;
+        self.items = match_search(&self.input, &results);
+        self.pos = 0;
+    }
+
+    pub async fn open(&mut self) {
+        self.enabled = true;
+        self.refresh().await;
+    }
+
+    // returns true once search closes and the screen needs a full redraw
+    pub async fn update(&mut self, action: Action, key: &KeyEvent) -> bool {
+        match action {
+            Action::Type => {
+                if let KeyCode::Char(c) = key.code {
+                    self.input.push(c);
+                }
+                self.refresh().await;
+                self.render(true).await;
+            }
+            Action::Erase => {
+                self.input.pop();
+                self.refresh().await;
+                self.render(true).await;
+            }
+            Action::Up => {
+                if self.pos + 1 >= self.items.len() && !self.pages.is_done() {
+                    let results = self.pages.next_page().await;
+                    self.items.extend(match_search(&self.input, &results));
+                }
+
+                if self.pos + 1 < self.items.len() {
+                    self.pos += 1
+                } else {
+                    self.pos = 0
+                }
+                self.render(false).await;
+            }
+            Action::Down => {
+                if self.pos > 0 {
+                    self.pos -= 1
+                } else {
+                    self.pos = self.items.len().saturating_sub(1)
+                }
+                self.render(false).await;
+            }
+            Action::Open => {
+                let Some((_, object)) = self.items.get_index(self.pos) else {
+                    return false;
+                };
+                self.chosen = Some(object.clone());
+                self.close();
+                return true;
+            }
+            Action::Back => {
+                self.close();
+                return true;
+            }
+            _ => {}
+        }
+        false
+    }
+
+    fn close(&mut self) {
+        self.input.clear();
+        self.enabled = false;
+        self.items.clear();
+        self.pos = 0;
+    }
+
+    pub async fn render(&self, input_update: bool) {
+        let placeholder = match &self.picking {
+            Some(page) => format!("move {} to...", page.title),
+            None => "search...".to_string(),
+        };
+        search_box(self.enabled, &self.input, &placeholder, &self.items, &self.pos, Some(input_update)).await;
+    }
+}
 
 pub async fn search_box(
     search_enabled: bool,
     search_input: &str,
-    search_items: &HashMap<String, Object>,
+    placeholder: &str,
+    search_items: &IndexMap<String, Object>,
     search_pos: &usize,
     input_update: Option<bool>,
 ) {
@@ -71,20 +189,19 @@ pub async fn search_box(
                 .unwrap();
 
                 if search_input.is_empty() {
-                    execute!(stdout(), Print("search...".to_string()), ResetColor).unwrap();
+                    execute!(stdout(), Print(placeholder.to_string()), ResetColor).unwrap();
                 } else {
                     let mut search_input_text = search_input.to_string();
 
                     if search_input.len() > (search_box_width as usize * 2 - 7) {
                         search_input_text = "...".to_string()
                             + &search_input
-                                [search_input.len() - (search_box_width as usize * 2 - 7)..]
-                                .to_string();
+                                [search_input.len() - (search_box_width as usize * 2 - 7)..];
                     }
 
                     execute!(
                         stdout(),
-                        SetForegroundColor(Color::Rgb {r: 49, g: 116, b: 143}),
+                        SetForegroundColor(accent_color()),
                         Print(search_input_text),
                         ResetColor
                     )
@@ -94,8 +211,10 @@ pub async fn search_box(
         }
 
         // Search items / results
-        for (si, page) in search_items.iter().enumerate() {
-            if si == *search_pos {
+        let count = search_box_height as usize - 3;
+        let start = count * (search_pos / count);
+        for (si, page) in search_items.iter().skip(start).take(count).enumerate() {
+            if si == search_pos % count {
                 execute!(
                     stdout(),
                     MoveTo(
@@ -103,11 +222,11 @@ pub async fn search_box(
                         y_search + search_box_height - 3 - (si as u16)
                     ),
                     SetForegroundColor(Color::White),
-                    SetBackgroundColor(Color::Rgb {r: 49, g: 116, b: 143}),
-                    Print(format!(" {}", page.1.title.to_string()).bold()),
-                    SetBackgroundColor(Color::Rgb {r: 49, g: 116, b: 143}),
+                    SetBackgroundColor(accent_color()),
+                    Print(format!(" {}", page.1.title).bold()),
+                    SetBackgroundColor(accent_color()),
                     SetForegroundColor(Color::DarkGrey),
-                    Print(" ".repeat((content_width - 2) as usize - page.1.title.to_string().len() as usize - 3)),
+                    Print(" ".repeat(((content_width - 2) as usize).saturating_sub(page.1.title.len() + 3))),
                     ResetColor
                 )
                 .unwrap()
@@ -159,9 +278,4 @@ pub async fn search_box(
         );
         write_ctrl(&search_text, x_search, y_search + 1);
     }
-
-    match get_pages().await {
-        Ok(_) => {}
-        Err(_) => {}
-    }
 }
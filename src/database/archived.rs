@@ -0,0 +1,73 @@
+use crate::{api::search::Object, database::get_connection};
+use chrono::Utc;
+use rusqlite::{params, Result};
+use serde::{Deserialize, Serialize};
+
+// search leaves out archived pages, so the ones archived from here are remembered
+// locally to list and restore them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Archived {
+    pub id: String,
+    pub parent_id: Option<String>,
+    pub title: String,
+    pub object_type: String,
+    pub archived_at: i64,
+}
+
+impl Archived {
+    pub fn to_object(&self) -> Object {
+        Object {
+            id: self.id.clone(),
+            parent_id: self.parent_id.clone(),
+            title: self.title.clone(),
+            object_type: self.object_type.clone(),
+            children: Vec::new(),
+            last_edited_time: None,
+        }
+    }
+}
+
+pub fn record_archived(object: &Object) {
+    let conn = get_connection();
+
+    conn.execute(
+        "INSERT INTO archived (id, parent_id, title, object_type, archived_at) VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(id) DO UPDATE SET parent_id = ?2, title = ?3, object_type = ?4, archived_at = ?5",
+        params![
+            object.id,
+            object.parent_id,
+            object.title,
+            object.object_type,
+            Utc::now().timestamp()
+        ],
+    )
+    .expect("Failed to save archived");
+}
+
+pub fn get_archived() -> Result<Vec<Archived>> {
+    let conn = get_connection();
+    let mut stmt = conn.prepare(
+        "SELECT id, parent_id, title, object_type, archived_at FROM archived ORDER BY archived_at DESC",
+    )?;
+
+    let archived = stmt
+        .query_map([], |row| {
+            Ok(Archived {
+                id: row.get(0)?,
+                parent_id: row.get(1)?,
+                title: row.get(2)?,
+                object_type: row.get(3)?,
+                archived_at: row.get(4)?,
+            })
+        })?
+        .collect();
+
+    archived
+}
+
+pub fn remove_archived(id: &str) {
+    let conn = get_connection();
+
+    conn.execute("DELETE FROM archived WHERE id = ?1", params![id])
+        .expect("Failed to remove archived");
+}
@@ -0,0 +1,34 @@
+use crate::{api::search::Object, database::get_connection};
+use rusqlite::{params, OptionalExtension, Result};
+
+// pages remembered under a name, like the inbox `append` writes to
+pub fn set_default(name: &str, object: &Object) {
+    let conn = get_connection();
+
+    conn.execute(
+        "INSERT INTO defaults (name, id, title, object_type) VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT(name) DO UPDATE SET id = ?2, title = ?3, object_type = ?4",
+        params![name, object.id, object.title, object.object_type],
+    )
+    .expect("Failed to save default");
+}
+
+pub fn get_default(name: &str) -> Result<Option<Object>> {
+    let conn = get_connection();
+
+    conn.query_row(
+        "SELECT id, title, object_type FROM defaults WHERE name = ?1",
+        params![name],
+        |row| {
+            Ok(Object {
+                id: row.get(0)?,
+                parent_id: None,
+                title: row.get(1)?,
+                object_type: row.get(2)?,
+                children: Vec::new(),
+                last_edited_time: None,
+            })
+        },
+    )
+    .optional()
+}
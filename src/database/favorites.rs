@@ -0,0 +1,81 @@
+use crate::{api::search::Object, database::get_connection};
+use rusqlite::{params, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Favorite {
+    pub id: String,
+    pub title: String,
+    pub object_type: String,
+    pub position: i64,
+}
+
+impl Favorite {
+    pub fn to_object(&self) -> Object {
+        Object {
+            id: self.id.clone(),
+            parent_id: None,
+            title: self.title.clone(),
+            object_type: self.object_type.clone(),
+            children: Vec::new(),
+            last_edited_time: None,
+        }
+    }
+}
+
+pub fn get_favorites() -> Result<Vec<Favorite>> {
+    let conn = get_connection();
+    let mut stmt = conn
+        .prepare("SELECT id, title, object_type, position FROM favorites ORDER BY position")?;
+
+    let favorites = stmt
+        .query_map([], |row| {
+            Ok(Favorite {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                object_type: row.get(2)?,
+                position: row.get(3)?,
+            })
+        })?
+        .collect();
+
+    favorites
+}
+
+// add the object to the end of the favorites, or remove it if already there
+pub fn toggle_favorite(object: &Object) {
+    let conn = get_connection();
+
+    let removed = conn
+        .execute("DELETE FROM favorites WHERE id = ?1", params![object.id])
+        .expect("Failed to remove favorite");
+
+    if removed == 0 {
+        conn.execute(
+            "INSERT INTO favorites (id, title, object_type, position) VALUES (?1, ?2, ?3, (SELECT IFNULL(MAX(position), -1) + 1 FROM favorites))",
+            params![object.id, object.title, object.object_type],
+        )
+        .expect("Failed to save favorite");
+    }
+}
+
+// swap a favorite with its neighbour above (-1) or below (1)
+pub fn move_favorite(favorites: &[Favorite], index: usize, delta: isize) {
+    let conn = get_connection();
+
+    let target = match index.checked_add_signed(delta) {
+        Some(target) if target < favorites.len() => target,
+        _ => return,
+    };
+
+    conn.execute(
+        "UPDATE favorites SET position = ?1 WHERE id = ?2",
+        params![favorites[target].position, favorites[index].id],
+    )
+    .expect("Failed to reorder favorites");
+    conn.execute(
+        "UPDATE favorites SET position = ?1 WHERE id = ?2",
+        params![favorites[index].position, favorites[target].id],
+    )
+    .expect("Failed to reorder favorites");
+}
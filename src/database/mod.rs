@@ -1,10 +1,17 @@
+pub mod archived;
+pub mod defaults;
+pub mod favorites;
+pub mod queries;
+pub mod recents;
+pub mod templates;
+pub mod todo_sources;
 pub mod user;
 
 use rusqlite::Connection;
 use user::get_user;
 use std::sync::Once;
 
-use crate::{auth_completed, set_auth};
+use crate::app::auth_completed;
 
 static INIT: Once = Once::new();
 static mut DATABASE: Option<Connection> = None;
@@ -23,17 +30,78 @@ pub fn initialize_db(db_path: &str) {
             )",
             [],
         ).expect("Failed to create table");
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS favorites (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                object_type TEXT NOT NULL,
+                position INTEGER NOT NULL
+            )",
+            [],
+        ).expect("Failed to create table");
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS recents (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                object_type TEXT NOT NULL,
+                accessed_at INTEGER NOT NULL
+            )",
+            [],
+        ).expect("Failed to create table");
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS defaults (
+                name TEXT PRIMARY KEY,
+                id TEXT NOT NULL,
+                title TEXT NOT NULL,
+                object_type TEXT NOT NULL
+            )",
+            [],
+        ).expect("Failed to create table");
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS archived (
+                id TEXT PRIMARY KEY,
+                parent_id TEXT,
+                title TEXT NOT NULL,
+                object_type TEXT NOT NULL,
+                archived_at INTEGER NOT NULL
+            )",
+            [],
+        ).expect("Failed to create table");
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS queries (
+                name TEXT PRIMARY KEY,
+                database_id TEXT NOT NULL,
+                database_title TEXT NOT NULL,
+                query TEXT NOT NULL
+            )",
+            [],
+        ).expect("Failed to create table");
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS todo_sources (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                object_type TEXT NOT NULL
+            )",
+            [],
+        ).expect("Failed to create table");
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS templates (
+                name TEXT PRIMARY KEY,
+                body TEXT NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        ).expect("Failed to create table");
         unsafe {
             DATABASE = Some(conn);
         }
     });
 
     if let Some(_user) = get_user().unwrap() {
-        set_auth(true);
         auth_completed();
     }
 }
 
 pub fn get_connection() -> &'static Connection {
-    unsafe { DATABASE.as_ref().expect("Database not initialized") }
+    unsafe { (*std::ptr::addr_of!(DATABASE)).as_ref().expect("Database not initialized") }
 }
@@ -0,0 +1,69 @@
+use crate::database::get_connection;
+use rusqlite::{params, OptionalExtension, Result};
+use serde::{Deserialize, Serialize};
+
+// a query kept under a name, tied to the database it was written for
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedQuery {
+    pub name: String,
+    pub database_id: String,
+    pub database_title: String,
+    pub query: String,
+}
+
+// names are shared by every database so `@name` always means one query. false
+// when the name is already taken by another database's query, which is left alone
+pub fn save_query(saved: &SavedQuery) -> bool {
+    let conn = get_connection();
+
+    conn.execute(
+        "INSERT INTO queries (name, database_id, database_title, query) VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT(name) DO UPDATE SET database_title = ?3, query = ?4
+            WHERE database_id = ?2",
+        params![saved.name, saved.database_id, saved.database_title, saved.query],
+    )
+    .expect("Failed to save query")
+        > 0
+}
+
+fn saved_query(row: &rusqlite::Row) -> Result<SavedQuery> {
+    Ok(SavedQuery {
+        name: row.get(0)?,
+        database_id: row.get(1)?,
+        database_title: row.get(2)?,
+        query: row.get(3)?,
+    })
+}
+
+pub fn get_query(name: &str) -> Result<Option<SavedQuery>> {
+    let conn = get_connection();
+
+    conn.query_row(
+        "SELECT name, database_id, database_title, query FROM queries WHERE name = ?1",
+        params![name],
+        saved_query,
+    )
+    .optional()
+}
+
+// every saved query, or only those for one database
+pub fn get_queries(database_id: Option<&str>) -> Result<Vec<SavedQuery>> {
+    let conn = get_connection();
+    let mut stmt = conn.prepare(
+        "SELECT name, database_id, database_title, query FROM queries
+            WHERE ?1 IS NULL OR database_id = ?1 ORDER BY name",
+    )?;
+
+    let queries = stmt.query_map(params![database_id], saved_query)?.collect();
+
+    queries
+}
+
+// true if there was a query with that name
+pub fn delete_query(name: &str) -> bool {
+    let conn = get_connection();
+
+    conn.execute("DELETE FROM queries WHERE name = ?1", params![name])
+        .expect("Failed to delete query")
+        > 0
+}
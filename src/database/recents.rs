@@ -0,0 +1,98 @@
+use crate::{api::search::Object, database::get_connection};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recent {
+    pub id: String,
+    pub title: String,
+    pub object_type: String,
+    pub accessed_at: i64,
+    pub edited: bool,
+}
+
+impl Recent {
+    pub fn to_object(&self) -> Object {
+        Object {
+            id: self.id.clone(),
+            parent_id: None,
+            title: self.title.clone(),
+            object_type: self.object_type.clone(),
+            children: Vec::new(),
+            last_edited_time: None,
+        }
+    }
+}
+
+pub fn record_recent(object: &Object) {
+    let conn = get_connection();
+
+    conn.execute(
+        "INSERT INTO recents (id, title, object_type, accessed_at) VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT(id) DO UPDATE SET title = ?2, object_type = ?3, accessed_at = ?4",
+        params![
+            object.id,
+            object.title,
+            object.object_type,
+            Utc::now().timestamp()
+        ],
+    )
+    .expect("Failed to save recent");
+}
+
+pub fn get_recents(limit: usize) -> Result<Vec<Recent>> {
+    let conn = get_connection();
+    let mut stmt = conn.prepare(
+        "SELECT id, title, object_type, accessed_at FROM recents ORDER BY accessed_at DESC LIMIT ?1",
+    )?;
+
+    let recents = stmt
+        .query_map(params![limit as i64], |row| {
+            Ok(Recent {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                object_type: row.get(2)?,
+                accessed_at: row.get(3)?,
+                edited: false,
+            })
+        })?
+        .collect();
+
+    recents
+}
+
+// interleave local opens with notion's own last edited times, newest first
+pub fn merge_edited(recents: Vec<Recent>, objects: &[Object], limit: usize) -> Vec<Recent> {
+    let mut merged = recents;
+
+    for object in objects {
+        let edited_at = match object
+            .last_edited_time
+            .as_deref()
+            .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+        {
+            Some(time) => time.timestamp(),
+            None => continue,
+        };
+
+        match merged.iter_mut().find(|recent| recent.id == object.id) {
+            Some(recent) if recent.accessed_at >= edited_at => {}
+            Some(recent) => {
+                recent.accessed_at = edited_at;
+                recent.edited = true;
+            }
+            None => merged.push(Recent {
+                id: object.id.clone(),
+                title: object.title.clone(),
+                object_type: object.object_type.clone(),
+                accessed_at: edited_at,
+                edited: true,
+            }),
+        }
+    }
+
+    merged.sort_by_key(|recent| -recent.accessed_at);
+    merged.truncate(limit);
+    merged
+}
@@ -0,0 +1,66 @@
+use crate::database::get_connection;
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension, Result};
+use serde::{Deserialize, Serialize};
+
+// a named page layout kept as markdown, with {{date}} and {{title}} placeholders
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Template {
+    pub name: String,
+    pub body: String,
+    pub updated_at: i64,
+}
+
+pub fn save_template(name: &str, body: &str) {
+    let conn = get_connection();
+
+    conn.execute(
+        "INSERT INTO templates (name, body, updated_at) VALUES (?1, ?2, ?3)
+            ON CONFLICT(name) DO UPDATE SET body = ?2, updated_at = ?3",
+        params![name, body, Utc::now().timestamp()],
+    )
+    .expect("Failed to save template");
+}
+
+pub fn get_template(name: &str) -> Result<Option<Template>> {
+    let conn = get_connection();
+
+    conn.query_row(
+        "SELECT name, body, updated_at FROM templates WHERE name = ?1",
+        params![name],
+        |row| {
+            Ok(Template {
+                name: row.get(0)?,
+                body: row.get(1)?,
+                updated_at: row.get(2)?,
+            })
+        },
+    )
+    .optional()
+}
+
+pub fn get_templates() -> Result<Vec<Template>> {
+    let conn = get_connection();
+    let mut stmt = conn.prepare("SELECT name, body, updated_at FROM templates ORDER BY name")?;
+
+    let templates = stmt
+        .query_map([], |row| {
+            Ok(Template {
+                name: row.get(0)?,
+                body: row.get(1)?,
+                updated_at: row.get(2)?,
+            })
+        })?
+        .collect();
+
+    templates
+}
+
+// true if there was a template with that name
+pub fn delete_template(name: &str) -> bool {
+    let conn = get_connection();
+
+    conn.execute("DELETE FROM templates WHERE name = ?1", params![name])
+        .expect("Failed to delete template")
+        > 0
+}
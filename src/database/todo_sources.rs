@@ -0,0 +1,51 @@
+use crate::{api::search::Object, database::get_connection};
+use rusqlite::{params, Result};
+
+// pages whose to-dos, and databases whose unfinished rows, make up the todos list
+pub fn get_todo_sources() -> Result<Vec<Object>> {
+    let conn = get_connection();
+    let mut stmt = conn.prepare("SELECT id, title, object_type FROM todo_sources ORDER BY title")?;
+
+    let sources = stmt
+        .query_map([], |row| {
+            Ok(Object {
+                id: row.get(0)?,
+                parent_id: None,
+                title: row.get(1)?,
+                object_type: row.get(2)?,
+                children: Vec::new(),
+                last_edited_time: None,
+            })
+        })?
+        .collect();
+
+    sources
+}
+
+pub fn add_todo_source(object: &Object) {
+    let conn = get_connection();
+
+    conn.execute(
+        "INSERT INTO todo_sources (id, title, object_type) VALUES (?1, ?2, ?3)
+            ON CONFLICT(id) DO UPDATE SET title = ?2, object_type = ?3",
+        params![object.id, object.title, object.object_type],
+    )
+    .expect("Failed to save todo source");
+}
+
+pub fn remove_todo_source(id: &str) -> bool {
+    let conn = get_connection();
+
+    conn.execute("DELETE FROM todo_sources WHERE id = ?1", params![id])
+        .expect("Failed to remove todo source")
+        > 0
+}
+
+// add the object as a source, or remove it if already one, returning whether it was added
+pub fn toggle_todo_source(object: &Object) -> bool {
+    if remove_todo_source(&object.id) {
+        return false;
+    }
+    add_todo_source(object);
+    true
+}
@@ -48,7 +48,7 @@ pub fn get_user() -> Result<Option<User>> {
     }) {
         Ok(user) => Ok(Some(user)),
         Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(e.into()),
+        Err(e) => Err(e),
     }
 }
 
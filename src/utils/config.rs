@@ -0,0 +1,90 @@
+use std::{env, fs, path::PathBuf, sync::Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::theme::reset_theme;
+
+pub const START_SECTIONS: [&str; 5] = ["home", "pages", "tables", "favorites", "recents"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub db_path: String,
+    pub notion_version: String,
+    pub search_page_size: usize,
+    pub start_section: String,
+    pub theme: String,
+    // strftime pattern for journal entry titles
+    pub journal_title: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            db_path: "./ncli.db".to_string(),
+            notion_version: "2022-06-28".to_string(),
+            search_page_size: 20,
+            start_section: "home".to_string(),
+            theme: "default".to_string(),
+            journal_title: "%Y-%m-%d".to_string(),
+        }
+    }
+}
+
+lazy_static::lazy_static! (
+    static ref CONFIG: Mutex<Option<Config>> = Mutex::new(None);
+    // why config.toml didn't parse, the defaults are used meanwhile
+    static ref PARSE_ERROR: Mutex<Option<String>> = Mutex::new(None);
+);
+
+// $XDG_CONFIG_HOME/notion-cli/config.toml, falling back to ~/.config
+pub fn config_path() -> PathBuf {
+    let config_dir = env::var("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| env::var("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(|_| PathBuf::from("."));
+
+    config_dir.join("notion-cli").join("config.toml")
+}
+
+fn load_config() -> Config {
+    let path = config_path();
+    let Ok(contents) = fs::read_to_string(&path) else {
+        return Config::default();
+    };
+    toml::from_str(&contents).unwrap_or_else(|e: toml::de::Error| {
+        let reason = e.to_string().lines().next().unwrap_or_default().to_string();
+        *PARSE_ERROR.lock().unwrap() = Some(format!("{} didn't parse: {}", path.display(), reason));
+        Config::default()
+    })
+}
+
+pub fn get_config() -> Config {
+    let mut config = CONFIG.lock().unwrap();
+    config.get_or_insert_with(load_config).clone()
+}
+
+pub fn config_error() -> Option<String> {
+    get_config();
+    PARSE_ERROR.lock().unwrap().clone()
+}
+
+// a file that didn't parse is left for the user to fix rather than replaced by defaults
+pub fn save_config(config: Config) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(error) = config_error() {
+        return Err(format!("not saving, {}", error).into());
+    }
+    let path = config_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, toml::to_string(&config)?)?;
+
+    *CONFIG.lock().unwrap() = Some(config);
+    reset_theme();
+    Ok(())
+}
+
+pub fn notion_version() -> String {
+    get_config().notion_version
+}
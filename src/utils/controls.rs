@@ -6,28 +6,24 @@ use crossterm::{cursor::MoveTo, execute, style::{Color, Print, ResetColor, SetFo
 pub fn write_ctrl(ctrl: &str, x: u16, y: u16) {
     let mut char_indices = ctrl.char_indices();
 
-    match char_indices.find(|(_, c)| c.to_string() == '['.to_string()) {
-        Some((i, _)) => match char_indices.find(|(_, c)| c.to_string() == ']'.to_string()) {
-            Some((j, _)) => {
-                let left = &ctrl[..i + 1];
-                let middle = &ctrl[i + 1..j];
-                let right = &ctrl[j - 1 + 1..];
+    if let Some((i, _)) = char_indices.find(|(_, c)| *c == '[') {
+        if let Some((j, _)) = char_indices.find(|(_, c)| *c == ']') {
+            let left = &ctrl[..i + 1];
+            let middle = &ctrl[i + 1..j];
+            let right = &ctrl[j..];
 
-                execute!(
-                    stdout(),
-                    MoveTo(x, y),
-                    SetForegroundColor(Color::DarkGrey),
-                    Print(left),
-                    SetForegroundColor(Color::White),
-                    Print(middle),
-                    SetForegroundColor(Color::DarkGrey),
-                    Print(right),
-                    ResetColor
-                )
-                .unwrap();
-            }
-            None => {}
-        },
-        None => {}
+            execute!(
+                stdout(),
+                MoveTo(x, y),
+                SetForegroundColor(Color::DarkGrey),
+                Print(left),
+                SetForegroundColor(Color::White),
+                Print(middle),
+                SetForegroundColor(Color::DarkGrey),
+                Print(right),
+                ResetColor
+            )
+            .unwrap();
+        }
     }
 }
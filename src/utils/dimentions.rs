@@ -2,7 +2,9 @@
 const MAX_WIDTH: u16 = 60;
 const MAX_HEIGHT: u16 = 30;
 
-use crossterm::terminal::size;
+use std::io::stdout;
+
+use crossterm::{execute, style::Print, terminal::size};
 
 // get terminal dimensions
 pub fn get_dimensions() -> (u16, u16, u16, u16) {
@@ -15,4 +17,8 @@ pub fn get_dimensions() -> (u16, u16, u16, u16) {
     let y_center = rows / 2;
 
     (content_width, content_height, x_center, y_center)
-}
\ No newline at end of file
+}
+pub fn set_scroll_region(top: u16, bottom: u16) {
+    let command = format!("\x1B[{};{}r", top + 1, bottom + 1);
+    execute!(stdout(), Print(command)).unwrap();
+}
@@ -0,0 +1,495 @@
+use std::{
+    collections::HashMap,
+    env,
+    error::Error,
+    fs,
+    io::{self, stdout},
+    path::Path,
+    process,
+};
+
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    execute,
+    style::Print,
+    terminal::{disable_raw_mode, enable_raw_mode, size, Clear, ClearType},
+};
+use serde_json::{json, Value};
+use tempfile::TempPath;
+
+use crate::{
+    api::blocks::{append_block_children, delete_block, get_block_children, update_block, Block, RichText},
+    utils::{
+        dimentions::set_scroll_region,
+        markdown::{blocks_to_markdown, parse_markdown},
+    },
+};
+
+// block types whose content can be replaced in place
+const UPDATABLE: [&str; 10] = [
+    "paragraph",
+    "heading_1",
+    "heading_2",
+    "heading_3",
+    "bulleted_list_item",
+    "numbered_list_item",
+    "to_do",
+    "quote",
+    "code",
+    "equation",
+];
+
+pub enum Edit {
+    Keep(String),
+    Update(String, Value),
+    Delete(String),
+    Insert(Vec<Value>),
+}
+
+// a block in the file, with the json its markdown parses to
+struct Entry {
+    id: String,
+    block_type: String,
+    parsed: Value,
+}
+
+// a page's blocks written out as markdown, remembering what each block parses back to
+pub struct EditDocument {
+    pub text: String,
+    entries: Vec<Entry>,
+    // the left out block just above the first one in the file, new text at the top goes after it
+    above: Option<String>,
+}
+
+fn has_subpages(block: &Block) -> bool {
+    block.block_type == "child_page"
+        || block.block_type == "child_database"
+        || block.children.iter().any(has_subpages)
+}
+
+// rich text json as spans, the way parse_rich_text reads what notion sends back
+fn spans(rich_text: &Value) -> Vec<RichText> {
+    let items = rich_text.as_array().cloned().unwrap_or_default();
+    joined(items.iter().map(|item| RichText {
+        text: item["text"]["content"]
+            .as_str()
+            .or(item["equation"]["expression"].as_str())
+            .unwrap_or("")
+            .to_string(),
+        bold: item["annotations"]["bold"].as_bool().unwrap_or(false),
+        italic: item["annotations"]["italic"].as_bool().unwrap_or(false),
+        strikethrough: item["annotations"]["strikethrough"].as_bool().unwrap_or(false),
+        underline: item["annotations"]["underline"].as_bool().unwrap_or(false),
+        code: item["annotations"]["code"].as_bool().unwrap_or(false),
+        href: item["text"]["link"]["url"].as_str().map(|url| url.to_string()),
+        ..Default::default()
+    }))
+}
+
+// runs with the same styling as one span, notion and parse_markdown split them differently
+fn joined(spans: impl IntoIterator<Item = RichText>) -> Vec<RichText> {
+    let styled = |span: &RichText| RichText { text: String::new(), ..span.clone() };
+    let mut joined = Vec::<RichText>::new();
+    for span in spans {
+        match joined.last_mut() {
+            Some(last) if styled(last) == styled(&span) => last.text.push_str(&span.text),
+            _ if span.text.is_empty() => {}
+            _ => joined.push(span),
+        }
+    }
+    joined
+}
+
+// whether a block parses back from its markdown as itself: the same type, text,
+// styling, mentions, checkbox, language, table cells and children
+fn survives(block: &Block, parsed: &Value) -> bool {
+    if parsed["type"] != block.block_type.as_str() {
+        return false;
+    }
+    let content = &parsed[&block.block_type];
+    let text = match block.block_type.as_str() {
+        "equation" => spans(&json!([{ "equation": { "expression": content["expression"] } }])),
+        _ => spans(&content["rich_text"]),
+    };
+    let cells = content["cells"].as_array().cloned().unwrap_or_default();
+    let children = content["children"].as_array().cloned().unwrap_or_default();
+
+    text == joined(block.text.iter().cloned())
+        && (block.block_type != "to_do" || content["checked"].as_bool() == Some(block.checked == Some(true)))
+        && (block.block_type != "code" || content["language"].as_str() == block.language.as_deref())
+        && cells.len() == block.cells.len()
+        && cells.iter().zip(&block.cells).all(|(cell, spans_in)| spans(cell) == joined(spans_in.iter().cloned()))
+        && children.len() == block.children.len()
+        && children.iter().zip(&block.children).all(|(parsed, child)| survives(child, parsed))
+}
+
+impl EditDocument {
+    // blocks that don't come back from markdown as themselves, and anything holding
+    // subpages, are left out so saving can never touch them
+    pub fn new(blocks: &[Block]) -> Self {
+        let links = HashMap::new();
+        let mut editable = Vec::<Block>::new();
+        let mut entries = Vec::<Entry>::new();
+        let mut above = None;
+
+        for block in blocks {
+            let mut parsed = match has_subpages(block) {
+                true => Vec::new(),
+                false => parse_markdown(&blocks_to_markdown(std::slice::from_ref(block), &links)),
+            };
+            if parsed.len() == 1 && survives(block, &parsed[0]) {
+                entries.push(Entry {
+                    id: block.id.clone(),
+                    block_type: block.block_type.clone(),
+                    parsed: parsed.remove(0),
+                });
+                editable.push(block.clone());
+            } else if entries.is_empty() {
+                above = Some(block.id.clone());
+            }
+        }
+
+        let mut text = blocks_to_markdown(&editable, &links);
+        text.push('\n');
+        EditDocument { text, entries, above }
+    }
+
+    pub fn left_out(&self, blocks: &[Block]) -> usize {
+        blocks.len() - self.entries.len()
+    }
+
+    // line up the blocks before and after by longest common subsequence
+    pub fn diff(&self, edited: &str) -> Vec<Edit> {
+        let old: Vec<&Value> = self.entries.iter().map(|entry| &entry.parsed).collect();
+        let new = parse_markdown(edited);
+
+        let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+        for i in (0..old.len()).rev() {
+            for j in (0..new.len()).rev() {
+                lengths[i][j] = if *old[i] == new[j] {
+                    lengths[i + 1][j + 1] + 1
+                } else {
+                    lengths[i + 1][j].max(lengths[i][j + 1])
+                };
+            }
+        }
+
+        let mut edits = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        let mut removed = Vec::<usize>::new();
+        let mut added = Vec::<Value>::new();
+        while i < old.len() || j < new.len() {
+            if i < old.len() && j < new.len() && *old[i] == new[j] {
+                self.settle(&mut edits, &mut removed, &mut added);
+                edits.push(Edit::Keep(self.entries[i].id.clone()));
+                i += 1;
+                j += 1;
+            } else if j < new.len() && (i == old.len() || lengths[i][j + 1] >= lengths[i + 1][j]) {
+                added.push(new[j].clone());
+                j += 1;
+            } else {
+                removed.push(i);
+                i += 1;
+            }
+        }
+        self.settle(&mut edits, &mut removed, &mut added);
+        self.place_top(edits)
+    }
+
+    // an update keeps the block, so it has to stay the same kind of block
+    fn updatable(&self, i: usize, block: &Value) -> bool {
+        let entry = &self.entries[i];
+        let block_type = block["type"].as_str().unwrap_or("");
+        entry.block_type == block_type
+            && entry.parsed["type"] == block_type
+            && UPDATABLE.contains(&block_type)
+            && entry.parsed[block_type]["children"].is_null()
+            && block[block_type]["children"].is_null()
+    }
+
+    // turn a run of removed and added blocks into updates where the types line up
+    fn settle(&self, edits: &mut Vec<Edit>, removed: &mut Vec<usize>, added: &mut Vec<Value>) {
+        let mut pairs = 0;
+        for (i, block) in removed.iter().zip(added.iter()) {
+            if !self.updatable(*i, block) {
+                break;
+            }
+            edits.push(Edit::Update(self.entries[*i].id.clone(), block.clone()));
+            pairs += 1;
+        }
+
+        for i in removed.drain(..).skip(pairs) {
+            edits.push(Edit::Delete(self.entries[i].id.clone()));
+        }
+        let inserted: Vec<Value> = added.drain(..).skip(pairs).collect();
+        if !inserted.is_empty() {
+            edits.push(Edit::Insert(inserted));
+        }
+    }
+
+    // the api only inserts after an existing block. text added at the top goes after the
+    // left out block above the file, or else after the first block, which is then
+    // written again below it and deleted
+    fn place_top(&self, mut edits: Vec<Edit>) -> Vec<Edit> {
+        let top = edits.iter().position(|edit| !matches!(edit, Edit::Delete(_)));
+        let Some(top) = top.filter(|&top| matches!(edits[top], Edit::Insert(_))) else {
+            return edits;
+        };
+        if let Some(above) = &self.above {
+            edits.insert(0, Edit::Keep(above.clone()));
+            return edits;
+        }
+
+        // an empty page takes the text as it is
+        let Some(first) = self.entries.first() else {
+            return edits;
+        };
+        let deleted = edits[..top].iter().any(|edit| matches!(edit, Edit::Delete(id) if *id == first.id));
+        if deleted {
+            edits.insert(0, Edit::Keep(first.id.clone()));
+            return edits;
+        }
+        let again = match edits.get(top + 1) {
+            Some(Edit::Keep(id)) if *id == first.id => first.parsed.clone(),
+            Some(Edit::Update(id, block)) if *id == first.id => block.clone(),
+            _ => return edits,
+        };
+        edits[top + 1] = Edit::Delete(first.id.clone());
+        if let Edit::Insert(blocks) = &mut edits[top] {
+            blocks.push(again);
+        }
+        edits.insert(0, Edit::Keep(first.id.clone()));
+        edits
+    }
+
+    // refuse the whole save up front rather than fail halfway through it
+    pub fn check(&self, edits: &[Edit]) -> Result<(), Box<dyn Error>> {
+        for edit in edits {
+            let Edit::Update(id, block) = edit else {
+                continue;
+            };
+            match self.entries.iter().position(|entry| entry.id == *id) {
+                Some(i) if self.updatable(i, block) => {}
+                Some(i) => {
+                    return Err(format!(
+                        "can't turn a {} block into a {} in place",
+                        self.entries[i].block_type,
+                        block["type"].as_str().unwrap_or("unknown")
+                    )
+                    .into())
+                }
+                None => return Err(format!("block {} isn't part of this edit", id).into()),
+            }
+        }
+        Ok(())
+    }
+}
+
+// apply edits in order, inserting after the last block that stays. deletes wait
+// until everything else went through, so a failed save never loses text.
+// returns how many blocks were changed, inserted and deleted
+pub async fn apply_edits(page_id: &str, edits: Vec<Edit>) -> Result<(usize, usize, usize), Box<dyn Error>> {
+    let mut anchor: Option<String> = None;
+    let mut deletes = Vec::<String>::new();
+    let (mut changed, mut inserted) = (0, 0);
+
+    for edit in edits {
+        match edit {
+            Edit::Keep(id) => anchor = Some(id),
+            Edit::Update(id, block) => {
+                update_block(&id, &block).await?;
+                changed += 1;
+                anchor = Some(id);
+            }
+            Edit::Delete(id) => deletes.push(id),
+            Edit::Insert(blocks) => {
+                inserted += blocks.len();
+                let ids = append_block_children(page_id, anchor.as_deref(), blocks).await?;
+                anchor = ids.last().cloned().or(anchor);
+            }
+        }
+    }
+
+    for id in &deletes {
+        delete_block(id).await?;
+    }
+    Ok((changed, inserted, deletes.len()))
+}
+
+// a page being edited through a temporary markdown file
+pub struct EditSession {
+    page_id: String,
+    document: EditDocument,
+    // removed again when the session is dropped
+    pub path: TempPath,
+    // blocks that were left out of the file
+    pub left_out: usize,
+}
+
+impl EditSession {
+    // the page is fetched again so the edit starts from what notion has now
+    pub async fn start(page_id: &str) -> Result<Self, Box<dyn Error>> {
+        let blocks = get_block_children(page_id).await?;
+        let document = EditDocument::new(&blocks);
+        // a fresh file only this user can read, named so nobody can guess it ahead of time
+        let file = tempfile::Builder::new().prefix("notion-").suffix(".md").tempfile()?;
+        fs::write(file.path(), &document.text)?;
+        let path = file.into_temp_path();
+
+        Ok(EditSession {
+            page_id: page_id.to_string(),
+            left_out: document.left_out(&blocks),
+            document,
+            path,
+        })
+    }
+
+    pub fn discard(self) {
+        let _ = self.path.close();
+    }
+
+    // sync whatever the editor left in the file, describing the outcome
+    pub async fn finish(self) -> Result<String, Box<dyn Error>> {
+        let edited = fs::read_to_string(&self.path);
+        let _ = self.path.close();
+        let edited = edited?;
+
+        if edited == self.document.text {
+            return Ok("no changes".to_string());
+        }
+        let edits = self.document.diff(&edited);
+        self.document.check(&edits)?;
+        let (changed, inserted, deleted) = apply_edits(&self.page_id, edits).await?;
+        Ok(format!("saved: {} changed, {} added, {} removed", changed, inserted, deleted))
+    }
+}
+
+// run $VISUAL or $EDITOR on a file, true if it exited cleanly
+pub fn launch_editor(path: &Path) -> io::Result<bool> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+
+    // through the shell so editors configured with arguments, like `code -w`, work
+    let status = process::Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg("sh")
+        .arg(path)
+        .status()?;
+    Ok(status.success())
+}
+
+// hand the terminal over to the editor, restoring the ui once it exits
+pub fn run_editor(path: &Path) -> io::Result<bool> {
+    disable_raw_mode()?;
+    execute!(stdout(), Show, MoveTo(0, 0), Clear(ClearType::All), Print("\x1B[r"))?;
+
+    let exited = launch_editor(path);
+
+    enable_raw_mode()?;
+    execute!(stdout(), Hide)?;
+    let (_, rows) = size()?;
+    set_scroll_region(0, rows - 1);
+
+    exited
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::blocks::RichText;
+
+    fn block(id: &str, block_type: &str, text: &str) -> Block {
+        Block {
+            id: id.to_string(),
+            block_type: block_type.to_string(),
+            text: vec![RichText { text: text.to_string(), ..Default::default() }],
+            checked: None,
+            language: None,
+            icon: None,
+            url: None,
+            cells: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn describe(edits: &[Edit]) -> Vec<String> {
+        edits
+            .iter()
+            .map(|edit| match edit {
+                Edit::Keep(id) => format!("keep {}", id),
+                Edit::Update(id, _) => format!("update {}", id),
+                Edit::Delete(id) => format!("delete {}", id),
+                Edit::Insert(blocks) => format!("insert {}", blocks.len()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn changed_text_updates_in_place() {
+        let document = EditDocument::new(&[block("a", "paragraph", "one"), block("b", "paragraph", "two")]);
+        let edits = document.diff("one\n\ntwo!\n");
+        assert_eq!(describe(&edits), ["keep a", "update b"]);
+        assert!(document.check(&edits).is_ok());
+    }
+
+    #[test]
+    fn callouts_are_left_out() {
+        let document = EditDocument::new(&[block("a", "paragraph", "one"), block("b", "callout", "note")]);
+        assert_eq!(document.text, "one\n");
+        let edits = document.diff("one\n\n> changed note\n");
+        assert_eq!(describe(&edits), ["keep a", "insert 1"]);
+    }
+
+    #[test]
+    fn styling_markdown_drops_is_left_out() {
+        let mut underlined = block("a", "paragraph", "one");
+        underlined.text[0].underline = true;
+        let mut red = block("b", "paragraph", "two");
+        red.text[0].color = Some("red".to_string());
+        let mut mention = block("c", "paragraph", "Sam");
+        mention.text[0].mention = Some("user".to_string());
+        let mut bold = block("d", "paragraph", "four");
+        bold.text[0].bold = true;
+
+        let blocks = [underlined, red, mention, bold];
+        let document = EditDocument::new(&blocks);
+        assert_eq!(document.text, "**four**\n");
+        assert_eq!(document.left_out(&blocks), 3);
+    }
+
+    #[test]
+    fn code_and_todos_stay_in() {
+        let mut code = block("a", "code", "let x = 1;\nlet y = 2;");
+        code.language = Some("rust".to_string());
+        let mut plain = block("b", "code", "text");
+        plain.language = Some("plain text".to_string());
+        let mut todo = block("c", "to_do", "ship it");
+        todo.checked = Some(true);
+
+        let blocks = [code, plain, todo];
+        assert_eq!(EditDocument::new(&blocks).left_out(&blocks), 0);
+    }
+
+    #[test]
+    fn mismatched_updates_are_refused() {
+        let document = EditDocument::new(&[block("a", "paragraph", "note")]);
+        let edits = vec![Edit::Update("a".to_string(), parse_markdown("> note").remove(0))];
+        assert!(document.check(&edits).is_err());
+    }
+
+    #[test]
+    fn text_at_the_top_rewrites_only_the_first_block() {
+        let document = EditDocument::new(&[block("a", "paragraph", "one"), block("b", "paragraph", "two")]);
+        let edits = document.diff("zero\n\none\n\ntwo\n");
+        assert_eq!(describe(&edits), ["keep a", "insert 2", "delete a", "keep b"]);
+    }
+
+    #[test]
+    fn text_at_the_top_goes_after_left_out_blocks() {
+        let document = EditDocument::new(&[block("p", "child_page", "sub"), block("a", "paragraph", "one")]);
+        let edits = document.diff("zero\n\none\n");
+        assert_eq!(describe(&edits), ["keep p", "insert 1", "keep a"]);
+    }
+}
@@ -22,10 +22,11 @@ pub fn initialize_server() {
 pub fn start_server() {
     unsafe {
         tokio::spawn(async move {
-            SERVER_HANDLE
-            .as_mut()
-            .expect("Server not initialized")
-            .start_server().await;
+            (*std::ptr::addr_of_mut!(SERVER_HANDLE))
+                .as_mut()
+                .expect("Server not initialized")
+                .start_server()
+                .await;
         });
     }
 }
@@ -33,7 +34,7 @@ pub fn start_server() {
 pub fn stop_server() {
     print!("Stopping server...");
     unsafe {
-        SERVER_HANDLE
+        (*std::ptr::addr_of_mut!(SERVER_HANDLE))
             .as_mut()
             .expect("Server not initialized")
             .stop_server();
@@ -92,5 +93,5 @@ fn authorize_with_code(code: &str) {
         }
     });
     
-    thread::spawn(move || stop_server());
+    thread::spawn(stop_server);
 }
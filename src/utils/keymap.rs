@@ -0,0 +1,248 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::app::Section;
+
+use Key::{AnyChar, Code, Shift};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    Type,
+    Erase,
+    Up,
+    Down,
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Open,
+    OpenPage,
+    NewPage,
+    Edit,
+    Cycle,
+    Archive,
+    Move,
+    Toggle,
+    Query,
+    Board,
+    Calendar,
+    Group,
+    SwitchView,
+    Today,
+    Save,
+    Favorite,
+    TodoSource,
+    ToggleEdits,
+    Back,
+    Search,
+    Home,
+    Help,
+    Login,
+    Pages,
+    Tables,
+    Favorites,
+    Recents,
+    Settings,
+    Trash,
+    Todos,
+    Journal,
+    Quit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Key {
+    Code(KeyCode),
+    Shift(KeyCode),
+    AnyChar,
+}
+
+// which input mode or sections a binding is live in
+#[derive(Debug, Clone, Copy)]
+pub enum Scope {
+    Search,
+    Editing,
+    Sections(&'static [Section]),
+    Global,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Search,
+    Editing,
+    Normal,
+}
+
+pub struct Binding {
+    pub key: Key,
+    pub scope: Scope,
+    pub action: Action,
+    pub description: &'static str,
+}
+
+const fn bind(key: Key, scope: Scope, action: Action, description: &'static str) -> Binding {
+    Binding {
+        key,
+        scope,
+        action,
+        description,
+    }
+}
+
+// the event loop dispatches on this table and the help overlay is drawn from it,
+// so section bindings must come before the global ones they shadow
+pub const BINDINGS: &[Binding] = &[
+    bind(AnyChar, Scope::Search, Action::Type, "type to search"),
+    bind(Code(KeyCode::Backspace), Scope::Search, Action::Erase, "delete a character"),
+    bind(Code(KeyCode::Up), Scope::Search, Action::Up, "next result"),
+    bind(Code(KeyCode::Down), Scope::Search, Action::Down, "previous result"),
+    bind(Code(KeyCode::Enter), Scope::Search, Action::Open, "open or pick the result"),
+    bind(Code(KeyCode::Esc), Scope::Search, Action::Back, "exit search"),
+    bind(AnyChar, Scope::Editing, Action::Type, "type a value"),
+    bind(Code(KeyCode::Backspace), Scope::Editing, Action::Erase, "delete a character"),
+    bind(Code(KeyCode::Enter), Scope::Editing, Action::Open, "save the value"),
+    bind(Code(KeyCode::Tab), Scope::Editing, Action::Cycle, "next template or saved query"),
+    bind(Code(KeyCode::Esc), Scope::Editing, Action::Back, "cancel editing"),
+    bind(Code(KeyCode::Up), Scope::Sections(&[Section::Reader]), Action::Up, "scroll up"),
+    bind(Code(KeyCode::Down), Scope::Sections(&[Section::Reader]), Action::Down, "scroll down"),
+    bind(Code(KeyCode::PageUp), Scope::Sections(&[Section::Reader]), Action::PageUp, "page up"),
+    bind(Code(KeyCode::PageDown), Scope::Sections(&[Section::Reader]), Action::PageDown, "page down"),
+    bind(Code(KeyCode::Char('e')), Scope::Sections(&[Section::Reader]), Action::Edit, "edit in $EDITOR"),
+    bind(Code(KeyCode::Up), Scope::Sections(&[Section::Grid]), Action::Up, "previous row"),
+    bind(Code(KeyCode::Down), Scope::Sections(&[Section::Grid]), Action::Down, "next row"),
+    bind(Code(KeyCode::PageUp), Scope::Sections(&[Section::Grid]), Action::PageUp, "previous rows"),
+    bind(Code(KeyCode::PageDown), Scope::Sections(&[Section::Grid]), Action::PageDown, "next rows"),
+    bind(Code(KeyCode::Left), Scope::Sections(&[Section::Grid]), Action::Left, "scroll columns left"),
+    bind(Code(KeyCode::Right), Scope::Sections(&[Section::Grid]), Action::Right, "scroll columns right"),
+    bind(Code(KeyCode::Enter), Scope::Sections(&[Section::Grid]), Action::Open, "open the row"),
+    bind(Code(KeyCode::Char('d')), Scope::Sections(&[Section::Grid]), Action::Archive, "archive the row"),
+    bind(Code(KeyCode::Char('e')), Scope::Sections(&[Section::Grid]), Action::Edit, "edit the row's properties"),
+    bind(Code(KeyCode::Char('/')), Scope::Sections(&[Section::Grid]), Action::Query, "filter and sort rows"),
+    bind(Code(KeyCode::Char('w')), Scope::Sections(&[Section::Grid]), Action::Save, "save the query"),
+    bind(Code(KeyCode::Char('b')), Scope::Sections(&[Section::Grid, Section::Tables]), Action::Board, "show as a board"),
+    bind(Code(KeyCode::Char('k')), Scope::Sections(&[Section::Grid, Section::Tables]), Action::Calendar, "show as a calendar"),
+    bind(Code(KeyCode::Left), Scope::Sections(&[Section::Board]), Action::Left, "previous lane"),
+    bind(Code(KeyCode::Right), Scope::Sections(&[Section::Board]), Action::Right, "next lane"),
+    bind(Code(KeyCode::Up), Scope::Sections(&[Section::Board]), Action::Up, "previous card"),
+    bind(Code(KeyCode::Down), Scope::Sections(&[Section::Board]), Action::Down, "next card"),
+    bind(Shift(KeyCode::Left), Scope::Sections(&[Section::Board]), Action::MoveLeft, "move the card a lane left"),
+    bind(Shift(KeyCode::Right), Scope::Sections(&[Section::Board]), Action::MoveRight, "move the card a lane right"),
+    bind(Code(KeyCode::Enter), Scope::Sections(&[Section::Board]), Action::Open, "open the card"),
+    bind(Code(KeyCode::Char('g')), Scope::Sections(&[Section::Board]), Action::Group, "group by the next property"),
+    bind(Code(KeyCode::Up), Scope::Sections(&[Section::Calendar]), Action::Up, "previous item or week"),
+    bind(Code(KeyCode::Down), Scope::Sections(&[Section::Calendar]), Action::Down, "next item or week"),
+    bind(Code(KeyCode::Left), Scope::Sections(&[Section::Calendar]), Action::Left, "previous week or day"),
+    bind(Code(KeyCode::Right), Scope::Sections(&[Section::Calendar]), Action::Right, "next week or day"),
+    bind(Code(KeyCode::PageUp), Scope::Sections(&[Section::Calendar]), Action::PageUp, "previous month"),
+    bind(Code(KeyCode::PageDown), Scope::Sections(&[Section::Calendar]), Action::PageDown, "next month"),
+    bind(Code(KeyCode::Enter), Scope::Sections(&[Section::Calendar]), Action::Open, "open the item or the day's agenda"),
+    bind(Code(KeyCode::Char('v')), Scope::Sections(&[Section::Calendar]), Action::SwitchView, "switch between agenda and month"),
+    bind(Code(KeyCode::Char('.')), Scope::Sections(&[Section::Calendar]), Action::Today, "jump to today"),
+    bind(Code(KeyCode::Char('g')), Scope::Sections(&[Section::Calendar]), Action::Group, "use the next date property"),
+    bind(Code(KeyCode::Up), Scope::Sections(&[Section::Row]), Action::Up, "previous property or option"),
+    bind(Code(KeyCode::Down), Scope::Sections(&[Section::Row]), Action::Down, "next property or option"),
+    bind(Code(KeyCode::Enter), Scope::Sections(&[Section::Row]), Action::Open, "edit, pick or save"),
+    bind(Code(KeyCode::Char(' ')), Scope::Sections(&[Section::Row]), Action::Toggle, "toggle an option"),
+    bind(Code(KeyCode::Char('o')), Scope::Sections(&[Section::Row]), Action::OpenPage, "open the row's page"),
+    bind(Shift(KeyCode::Up), Scope::Sections(&[Section::Favorites]), Action::MoveUp, "move favorite up"),
+    bind(Shift(KeyCode::Down), Scope::Sections(&[Section::Favorites]), Action::MoveDown, "move favorite down"),
+    bind(Code(KeyCode::Up), Scope::Sections(&[Section::Pages, Section::Tables, Section::Favorites, Section::Recents, Section::Trash, Section::Todos, Section::Settings]), Action::Up, "previous item"),
+    bind(Code(KeyCode::Down), Scope::Sections(&[Section::Pages, Section::Tables, Section::Favorites, Section::Recents, Section::Trash, Section::Todos, Section::Settings]), Action::Down, "next item"),
+    bind(Code(KeyCode::Enter), Scope::Sections(&[Section::Pages]), Action::Open, "open or expand"),
+    bind(Code(KeyCode::Char('o')), Scope::Sections(&[Section::Pages]), Action::OpenPage, "open the highlighted page"),
+    bind(Code(KeyCode::Char('n')), Scope::Sections(&[Section::Pages]), Action::NewPage, "new page under the highlighted item"),
+    bind(Code(KeyCode::Char('d')), Scope::Sections(&[Section::Pages]), Action::Archive, "archive the highlighted item"),
+    bind(Code(KeyCode::Char('v')), Scope::Sections(&[Section::Pages]), Action::Move, "move the highlighted page"),
+    bind(Code(KeyCode::Char('+')), Scope::Sections(&[Section::Pages]), Action::TodoSource, "collect todos from the highlighted item"),
+    bind(Code(KeyCode::Enter), Scope::Sections(&[Section::Tables, Section::Favorites, Section::Recents]), Action::Open, "open"),
+    bind(Code(KeyCode::Char('*')), Scope::Sections(&[Section::Pages, Section::Tables]), Action::Favorite, "toggle favorite"),
+    bind(Code(KeyCode::Char('*')), Scope::Sections(&[Section::Favorites]), Action::Favorite, "remove favorite"),
+    bind(Code(KeyCode::Char('e')), Scope::Sections(&[Section::Recents]), Action::ToggleEdits, "show/hide notion edits"),
+    bind(Code(KeyCode::Enter), Scope::Sections(&[Section::Trash]), Action::Open, "restore"),
+    bind(Code(KeyCode::Char(' ')), Scope::Sections(&[Section::Todos]), Action::Toggle, "check off"),
+    bind(Code(KeyCode::Enter), Scope::Sections(&[Section::Todos]), Action::Open, "open the page"),
+    bind(Code(KeyCode::Enter), Scope::Sections(&[Section::Settings]), Action::Open, "edit or cycle"),
+    bind(Code(KeyCode::Left), Scope::Sections(&[Section::Settings]), Action::Left, "previous choice"),
+    bind(Code(KeyCode::Right), Scope::Sections(&[Section::Settings]), Action::Right, "next choice"),
+    bind(Code(KeyCode::Char('s')), Scope::Global, Action::Search, "search"),
+    bind(Code(KeyCode::Esc), Scope::Global, Action::Back, "go back"),
+    bind(Code(KeyCode::Char('m')), Scope::Global, Action::Home, "menu"),
+    bind(Code(KeyCode::Char('h')), Scope::Global, Action::Help, "help"),
+    bind(Code(KeyCode::Char('l')), Scope::Global, Action::Login, "login/logout"),
+    bind(Code(KeyCode::Char('p')), Scope::Global, Action::Pages, "pages"),
+    bind(Code(KeyCode::Char('t')), Scope::Global, Action::Tables, "tables"),
+    bind(Code(KeyCode::Char('f')), Scope::Global, Action::Favorites, "favorites"),
+    bind(Code(KeyCode::Char('r')), Scope::Global, Action::Recents, "recents"),
+    bind(Code(KeyCode::Char('c')), Scope::Global, Action::Settings, "settings"),
+    bind(Code(KeyCode::Char('a')), Scope::Global, Action::Trash, "archived pages"),
+    bind(Code(KeyCode::Char('x')), Scope::Global, Action::Todos, "todos"),
+    bind(Code(KeyCode::Char('j')), Scope::Global, Action::Journal, "today's journal entry"),
+    bind(Code(KeyCode::Char('q')), Scope::Global, Action::Quit, "quit"),
+];
+
+impl Key {
+    fn matches(&self, event: &KeyEvent) -> bool {
+        let shift = event.modifiers.contains(KeyModifiers::SHIFT);
+        match self {
+            Key::AnyChar => matches!(event.code, KeyCode::Char(_)),
+            Key::Shift(code) => shift && event.code == *code,
+            // shift is part of the character itself for keys like `*`
+            Key::Code(code @ KeyCode::Char(_)) => event.code == *code,
+            Key::Code(code) => !shift && event.code == *code,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Key::AnyChar => "text".to_string(),
+            Key::Shift(code) => format!("shift+{}", code_label(code)),
+            Key::Code(code) => code_label(code),
+        }
+    }
+}
+
+fn code_label(code: &KeyCode) -> String {
+    match code {
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Enter => "enter".to_string(),
+        KeyCode::Esc => "esc".to_string(),
+        KeyCode::Tab => "tab".to_string(),
+        KeyCode::Backspace => "backspace".to_string(),
+        KeyCode::PageUp => "pgup".to_string(),
+        KeyCode::PageDown => "pgdn".to_string(),
+        _ => "?".to_string(),
+    }
+}
+
+impl Scope {
+    fn applies(&self, mode: Mode, section: Section) -> bool {
+        match (self, mode) {
+            (Scope::Search, Mode::Search) | (Scope::Editing, Mode::Editing) => true,
+            (Scope::Sections(sections), Mode::Normal) => sections.contains(&section),
+            (Scope::Global, Mode::Normal) => true,
+            _ => false,
+        }
+    }
+}
+
+// bindings live in the current mode and section, in dispatch order
+pub fn bindings_for(mode: Mode, section: Section) -> Vec<&'static Binding> {
+    BINDINGS
+        .iter()
+        .filter(|binding| binding.scope.applies(mode, section))
+        .collect()
+}
+
+pub fn lookup(event: &KeyEvent, mode: Mode, section: Section) -> Option<Action> {
+    bindings_for(mode, section)
+        .into_iter()
+        .find(|binding| binding.key.matches(event))
+        .map(|binding| binding.action)
+}
@@ -0,0 +1,816 @@
+use std::collections::HashMap;
+
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use serde_json::{json, Map, Value};
+
+use crate::api::blocks::{Block, RichText};
+
+// backslash escape characters that would otherwise start markdown syntax
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '~' | '|') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// backslash escape markers that start a block at the beginning of a line: headings,
+// list items, setext underlines and numbered items. `>`, `*` and fences are already
+// escaped wherever they appear
+fn escape_line_start(line: &str) -> String {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    // four spaces would be code, but only at the start of a block
+    if indent > 3 {
+        return line.to_string();
+    }
+    let (spaces, rest) = line.split_at(indent);
+    let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+    if rest.starts_with(['#', '-', '+', '=']) {
+        format!("{}\\{}", spaces, rest)
+    } else if (1..=9).contains(&digits) && rest[digits..].starts_with(['.', ')']) {
+        format!("{}{}\\{}", spaces, &rest[..digits], &rest[digits..])
+    } else {
+        line.to_string()
+    }
+}
+
+// plain text that reads back as itself wherever it is dropped into markdown
+pub fn escape_markdown(text: &str) -> String {
+    text.split('\n')
+        .map(|line| escape_line_start(&escape(line)))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// wrap a span in emphasis markers, keeping surrounding whitespace outside them
+fn emphasize(text: &str, marker: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return text.to_string();
+    }
+    let start = text.len() - text.trim_start().len();
+    let end = start + trimmed.len();
+    format!("{}{}{}{}{}", &text[..start], marker, trimmed, marker, &text[end..])
+}
+
+pub fn rich_text_to_markdown(spans: &[RichText]) -> String {
+    let markdown: String = spans
+        .iter()
+        .map(|span| {
+            let mut text = if span.code {
+                let fence = if span.text.contains('`') { "``" } else { "`" };
+                emphasize(&span.text, fence)
+            } else {
+                escape(&span.text)
+            };
+            if span.bold {
+                text = emphasize(&text, "**");
+            }
+            if span.italic {
+                text = emphasize(&text, "_");
+            }
+            if span.strikethrough {
+                text = emphasize(&text, "~~");
+            }
+            // user and date mentions read fine as their plain text
+            match (&span.href, span.mention.as_deref()) {
+                (Some(href), None | Some("page") | Some("database") | Some("link_preview")) => {
+                    format!("[{}](<{}>)", text, href)
+                }
+                _ => text,
+            }
+        })
+        .collect();
+    markdown
+        .split('\n')
+        .map(escape_line_start)
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn plain_text(spans: &[RichText]) -> String {
+    spans.iter().map(|span| span.text.as_str()).collect()
+}
+
+fn table_row(cells: &[Vec<RichText>]) -> String {
+    let cells: Vec<String> = cells
+        .iter()
+        .map(|cell| rich_text_to_markdown(cell).replace('\n', " "))
+        .collect();
+    format!("| {} |", cells.join(" | "))
+}
+
+// prefix every line after the first, used for list item continuations
+fn indent_rest(text: &str, indent: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 || line.is_empty() {
+                line.to_string()
+            } else {
+                format!("{}{}", indent, line)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn prefix_lines(text: &str, prefix: &str) -> String {
+    text.lines()
+        .map(|line| format!("{}{}", prefix, line).trim_end().to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// `links` maps child page ids to the relative path they were exported to
+pub fn blocks_to_markdown(blocks: &[Block], links: &HashMap<String, String>) -> String {
+    let mut sections = Vec::<String>::new();
+    let mut number = 0;
+    // consecutive list items are kept tight instead of separated by blank lines
+    let mut in_list = false;
+
+    for block in blocks {
+        number = if block.block_type == "numbered_list_item" { number + 1 } else { 0 };
+
+        let text = rich_text_to_markdown(&block.text);
+        let children = blocks_to_markdown(&block.children, links);
+
+        let (markdown, list_item) = match block.block_type.as_str() {
+            "paragraph" => (text, false),
+            "heading_1" => (format!("# {}", text), false),
+            "heading_2" => (format!("## {}", text), false),
+            "heading_3" => (format!("### {}", text), false),
+            "bulleted_list_item" | "toggle" => (format!("- {}", text), true),
+            "numbered_list_item" => (format!("{}. {}", number, text), true),
+            "to_do" => {
+                let mark = if block.checked == Some(true) { "x" } else { " " };
+                (format!("- [{}] {}", mark, text), true)
+            }
+            "quote" => (prefix_lines(&text, "> "), false),
+            "callout" => {
+                let icon = block.icon.clone().map(|icon| format!("{} ", icon)).unwrap_or_default();
+                (prefix_lines(&format!("{}{}", icon, text), "> "), false)
+            }
+            "code" => {
+                let fence = if plain_text(&block.text).contains("```") { "````" } else { "```" };
+                (
+                    format!(
+                        "{}{}\n{}\n{}",
+                        fence,
+                        block.language.clone().unwrap_or_default(),
+                        plain_text(&block.text),
+                        fence
+                    ),
+                    false,
+                )
+            }
+            "equation" => (format!("$$\n{}\n$$", plain_text(&block.text)), false),
+            "divider" => ("---".to_string(), false),
+            "image" => (
+                format!("![{}](<{}>)", plain_text(&block.text), block.url.clone().unwrap_or_default()),
+                false,
+            ),
+            "video" | "file" | "pdf" | "bookmark" | "embed" | "link_preview" => {
+                let url = block.url.clone().unwrap_or_default();
+                let label = if block.text.is_empty() { escape(&url) } else { text };
+                (format!("[{}](<{}>)", label, url), false)
+            }
+            "child_page" => {
+                let title = escape(&plain_text(&block.text));
+                match links.get(&block.id) {
+                    Some(path) => (format!("[{}](<{}>)", title, path), false),
+                    None => (format!("[{}](<https://www.notion.so/{}>)", title, block.id.replace('-', "")), false),
+                }
+            }
+            "child_database" => (
+                format!(
+                    "[{}](<https://www.notion.so/{}>)",
+                    escape(&plain_text(&block.text)),
+                    block.id.replace('-', "")
+                ),
+                false,
+            ),
+            "table" => {
+                let mut rows = block.children.iter().map(|row| table_row(&row.cells));
+                let header = rows.next().unwrap_or_default();
+                let columns = block.children.first().map_or(0, |row| row.cells.len());
+                let rule = format!("|{}", " --- |".repeat(columns));
+                let mut lines = vec![header, rule];
+                lines.extend(rows);
+                sections.push(lines.join("\n"));
+                in_list = false;
+                continue;
+            }
+            "column_list" | "column" | "synced_block" => {
+                if !children.is_empty() {
+                    sections.push(children);
+                }
+                in_list = false;
+                continue;
+            }
+            _ => continue,
+        };
+
+        let markdown = match block.block_type.as_str() {
+            _ if children.is_empty() => markdown,
+            "numbered_list_item" => {
+                let indent = " ".repeat(format!("{}. ", number).len());
+                format!("{}\n{}", markdown, indent_rest(&format!("\n{}", children), &indent))
+            }
+            _ if list_item => format!("{}\n{}", markdown, indent_rest(&format!("\n{}", children), "  ")),
+            "quote" | "callout" => format!("{}\n>\n{}", markdown, prefix_lines(&children, "> ")),
+            _ => format!("{}\n\n{}", markdown, children),
+        };
+
+        match sections.last_mut() {
+            Some(last) if in_list && list_item => {
+                last.push('\n');
+                last.push_str(&markdown);
+            }
+            _ => sections.push(markdown),
+        }
+        in_list = list_item;
+    }
+
+    sections.join("\n\n")
+}
+
+// code block languages the notion api accepts, anything else is rejected
+const LANGUAGES: [&str; 72] = [
+    "abap", "arduino", "bash", "basic", "c", "clojure", "coffeescript", "c++", "c#", "css",
+    "dart", "diff", "docker", "elixir", "elm", "erlang", "flow", "fortran", "f#", "gherkin",
+    "glsl", "go", "graphql", "groovy", "haskell", "html", "java", "javascript", "json", "julia",
+    "kotlin", "latex", "less", "lisp", "livescript", "lua", "makefile", "markdown", "markup",
+    "matlab", "mermaid", "nix", "objective-c", "ocaml", "pascal", "perl", "php", "plain text",
+    "powershell", "prolog", "protobuf", "python", "r", "reason", "ruby", "rust", "sass", "scala",
+    "scheme", "scss", "shell", "sql", "swift", "typescript", "vb.net", "verilog", "vhdl",
+    "visual basic", "webassembly", "xml", "yaml", "java/c/c++/c#",
+];
+
+fn code_language(info: &str) -> String {
+    let language = info.split_whitespace().next().unwrap_or("").to_lowercase();
+    let language = match language.as_str() {
+        "js" | "jsx" => "javascript",
+        "ts" | "tsx" => "typescript",
+        "py" => "python",
+        "rs" => "rust",
+        "rb" => "ruby",
+        "sh" | "zsh" | "console" => "shell",
+        "yml" => "yaml",
+        "cpp" | "cc" => "c++",
+        "cs" | "csharp" => "c#",
+        "md" => "markdown",
+        "dockerfile" => "docker",
+        "tex" => "latex",
+        "kt" => "kotlin",
+        other => other,
+    };
+    if LANGUAGES.contains(&language) {
+        language.to_string()
+    } else {
+        "plain text".to_string()
+    }
+}
+
+// notion only links to absolute urls, relative ones stay plain text
+fn is_url(url: &str) -> bool {
+    url.contains("://") || url.starts_with("mailto:")
+}
+
+#[derive(Default)]
+struct Style {
+    bold: usize,
+    italic: usize,
+    strikethrough: usize,
+    link: Option<String>,
+}
+
+impl Style {
+    fn text(&self, content: &str, code: bool) -> Vec<Value> {
+        let link = self.link.as_ref().map(|url| json!({ "url": url }));
+        let chars: Vec<char> = content.chars().collect();
+        // a single rich text item holds at most 2000 characters
+        chars
+            .chunks(2000)
+            .map(|chunk| {
+                json!({
+                    "type": "text",
+                    "text": { "content": chunk.iter().collect::<String>(), "link": link },
+                    "annotations": {
+                        "bold": self.bold > 0,
+                        "italic": self.italic > 0,
+                        "strikethrough": self.strikethrough > 0,
+                        "code": code,
+                    },
+                })
+            })
+            .collect()
+    }
+}
+
+struct Pending {
+    block_type: String,
+    rich_text: Vec<Value>,
+    content: Map<String, Value>,
+    cells: Vec<Vec<Value>>,
+    children: Vec<Value>,
+}
+
+impl Pending {
+    fn new(block_type: &str) -> Self {
+        Pending {
+            block_type: block_type.to_string(),
+            rich_text: Vec::new(),
+            content: Map::new(),
+            cells: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.rich_text.is_empty() && self.children.is_empty()
+    }
+
+    fn finish(self) -> Value {
+        let mut content = self.content;
+        match self.block_type.as_str() {
+            "divider" | "equation" => {}
+            "table_row" => {
+                content.insert("cells".to_string(), json!(self.cells));
+            }
+            "table" => {
+                let width = self.children.first().map_or(0, |row| {
+                    row["table_row"]["cells"].as_array().map_or(0, |cells| cells.len())
+                });
+                content.insert("table_width".to_string(), json!(width));
+                content.insert("has_column_header".to_string(), json!(true));
+                content.insert("has_row_header".to_string(), json!(false));
+                content.insert("children".to_string(), json!(self.children));
+            }
+            "image" => {
+                content.insert("caption".to_string(), json!(self.rich_text));
+            }
+            _ => {
+                content.insert("rich_text".to_string(), json!(self.rich_text));
+                if !self.children.is_empty() {
+                    content.insert("children".to_string(), json!(self.children));
+                }
+            }
+        }
+        json!({ "object": "block", "type": self.block_type, self.block_type.clone(): content })
+    }
+}
+
+// join runs with the same styling, notion caps a block at 100 rich text items
+fn merge_rich_text(target: &mut Vec<Value>, rich_text: Vec<Value>) {
+    for item in rich_text {
+        if let Some(last) = target.last_mut() {
+            let joined = format!(
+                "{}{}",
+                last["text"]["content"].as_str().unwrap_or(""),
+                item["text"]["content"].as_str().unwrap_or("")
+            );
+            let same = last["type"] == "text"
+                && item["type"] == "text"
+                && last["annotations"] == item["annotations"]
+                && last["text"]["link"] == item["text"]["link"];
+            if same && joined.chars().count() <= 2000 {
+                last["text"]["content"] = json!(joined);
+                continue;
+            }
+        }
+        target.push(item);
+    }
+}
+
+enum Frame {
+    Block(Pending),
+    List(bool),
+    // a paragraph whose text belongs to the enclosing list item or quote
+    Inline,
+    // an image that can't be embedded, its alt text stays in the paragraph
+    Skip,
+}
+
+#[derive(Default)]
+struct Builder {
+    frames: Vec<Frame>,
+    blocks: Vec<Value>,
+    style: Style,
+}
+
+impl Builder {
+    fn block_frames(&mut self) -> impl Iterator<Item = &mut Pending> {
+        self.frames.iter_mut().rev().filter_map(|frame| match frame {
+            Frame::Block(pending) => Some(pending),
+            _ => None,
+        })
+    }
+
+    fn push_rich_text(&mut self, rich_text: Vec<Value>) {
+        if let Some(pending) = self.block_frames().next() {
+            if pending.block_type == "table_row" {
+                if let Some(cell) = pending.cells.last_mut() {
+                    merge_rich_text(cell, rich_text);
+                }
+            } else {
+                merge_rich_text(&mut pending.rich_text, rich_text);
+            }
+        }
+    }
+
+    fn push_text(&mut self, text: &str, code: bool) {
+        let rich_text = self.style.text(text, code);
+        self.push_rich_text(rich_text);
+    }
+
+    // append to the children of the `skip`th enclosing block, or to the page itself
+    fn emit_at(&mut self, skip: usize, block: Value) {
+        let parent = self
+            .frames
+            .iter_mut()
+            .rev()
+            .filter_map(|frame| match frame {
+                Frame::Block(pending) => Some(pending),
+                _ => None,
+            })
+            .nth(skip);
+        match parent {
+            Some(parent) => parent.children.push(block),
+            None => self.blocks.push(block),
+        }
+    }
+
+    fn emit(&mut self, block: Value) {
+        self.emit_at(0, block);
+    }
+
+    // images and equations are blocks of their own, so they go beside the open paragraph
+    fn emit_beside(&mut self, block: Value) {
+        let in_paragraph = self
+            .block_frames()
+            .next()
+            .is_some_and(|pending| pending.block_type == "paragraph");
+        self.emit_at(if in_paragraph { 1 } else { 0 }, block);
+    }
+
+    fn open(&mut self, pending: Pending) {
+        self.frames.push(Frame::Block(pending));
+    }
+
+    fn close(&mut self) {
+        let Some(Frame::Block(mut pending)) = self.frames.pop() else {
+            return;
+        };
+        if pending.block_type == "paragraph" && pending.is_empty() {
+            return;
+        }
+        // fenced code always ends in a newline notion would show as a blank line
+        if pending.block_type == "code" {
+            if let Some(last) = pending.rich_text.last_mut() {
+                let content = last["text"]["content"].as_str().unwrap_or("").trim_end_matches('\n').to_string();
+                last["text"]["content"] = json!(content);
+            }
+        }
+        if pending.block_type == "image" {
+            return self.emit_beside(pending.finish());
+        }
+        self.emit(pending.finish());
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => {
+                let inline = matches!(
+                    self.frames.last(),
+                    Some(Frame::Block(pending))
+                        if pending.is_empty()
+                            && matches!(
+                                pending.block_type.as_str(),
+                                "bulleted_list_item" | "numbered_list_item" | "to_do" | "quote"
+                            )
+                );
+                if inline {
+                    self.frames.push(Frame::Inline);
+                } else {
+                    self.open(Pending::new("paragraph"));
+                }
+            }
+            Tag::Heading { level, .. } => self.open(Pending::new(match level {
+                HeadingLevel::H1 => "heading_1",
+                HeadingLevel::H2 => "heading_2",
+                _ => "heading_3",
+            })),
+            Tag::BlockQuote(_) => self.open(Pending::new("quote")),
+            Tag::CodeBlock(kind) => {
+                let info = match kind {
+                    CodeBlockKind::Fenced(info) => info.to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                let mut pending = Pending::new("code");
+                pending.content.insert("language".to_string(), json!(code_language(&info)));
+                self.open(pending);
+            }
+            Tag::HtmlBlock => self.open(Pending::new("paragraph")),
+            Tag::List(start) => self.frames.push(Frame::List(start.is_some())),
+            Tag::Item => {
+                let ordered = self.frames.iter().rev().find_map(|frame| match frame {
+                    Frame::List(ordered) => Some(*ordered),
+                    _ => None,
+                });
+                let block_type = if ordered == Some(true) {
+                    "numbered_list_item"
+                } else {
+                    "bulleted_list_item"
+                };
+                self.open(Pending::new(block_type));
+            }
+            Tag::Table(_) => self.open(Pending::new("table")),
+            Tag::TableHead | Tag::TableRow => self.open(Pending::new("table_row")),
+            Tag::TableCell => {
+                if let Some(row) = self.block_frames().next() {
+                    row.cells.push(Vec::new());
+                }
+            }
+            Tag::Emphasis => self.style.italic += 1,
+            Tag::Strong => self.style.bold += 1,
+            Tag::Strikethrough => self.style.strikethrough += 1,
+            Tag::Link { dest_url, .. } => {
+                self.style.link = is_url(&dest_url).then(|| dest_url.to_string());
+            }
+            Tag::Image { dest_url, .. } => {
+                if is_url(&dest_url) {
+                    let mut pending = Pending::new("image");
+                    pending.content.insert("type".to_string(), json!("external"));
+                    pending
+                        .content
+                        .insert("external".to_string(), json!({ "url": dest_url.to_string() }));
+                    self.open(pending);
+                } else {
+                    self.frames.push(Frame::Skip);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph
+            | TagEnd::Heading(_)
+            | TagEnd::BlockQuote(_)
+            | TagEnd::CodeBlock
+            | TagEnd::HtmlBlock
+            | TagEnd::List(_)
+            | TagEnd::Item
+            | TagEnd::Table
+            | TagEnd::TableHead
+            | TagEnd::TableRow
+            | TagEnd::Image => self.close(),
+            TagEnd::Emphasis => self.style.italic -= 1,
+            TagEnd::Strong => self.style.bold -= 1,
+            TagEnd::Strikethrough => self.style.strikethrough -= 1,
+            TagEnd::Link => self.style.link = None,
+            _ => {}
+        }
+    }
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) | Event::Html(text) | Event::InlineHtml(text) => {
+                self.push_text(&text, false)
+            }
+            Event::Code(text) => self.push_text(&text, true),
+            Event::InlineMath(expression) => self.push_rich_text(vec![json!({
+                "type": "equation",
+                "equation": { "expression": expression.to_string() },
+            })]),
+            Event::DisplayMath(expression) => {
+                let mut equation = Pending::new("equation");
+                equation.content.insert("expression".to_string(), json!(expression.trim()));
+                self.emit_beside(equation.finish());
+            }
+            Event::SoftBreak => self.push_text(" ", false),
+            Event::HardBreak => self.push_text("\n", false),
+            Event::Rule => self.emit(Pending::new("divider").finish()),
+            Event::TaskListMarker(checked) => {
+                if let Some(item) = self.block_frames().next() {
+                    item.block_type = "to_do".to_string();
+                    item.content.insert("checked".to_string(), json!(checked));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn plain_rich_text(rich_text: &Value) -> String {
+    rich_text
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item["text"]["content"].as_str())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// parse markdown into notion block json
+pub fn parse_markdown(markdown: &str) -> Vec<Value> {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_MATH;
+
+    let mut builder = Builder::default();
+    for event in Parser::new_ext(markdown, options) {
+        builder.event(event);
+    }
+    builder.blocks
+}
+
+// like parse_markdown, but a leading `# heading` is taken as the page title
+pub fn markdown_to_blocks(markdown: &str) -> (Option<String>, Vec<Value>) {
+    let mut blocks = parse_markdown(markdown);
+
+    let title = match blocks.first() {
+        Some(block) if block["type"] == "heading_1" => {
+            Some(plain_rich_text(&block["heading_1"]["rich_text"]))
+        }
+        _ => None,
+    };
+    if title.is_some() {
+        blocks.remove(0);
+    }
+    (title, blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // notion block json as the api would hand it back, enough for blocks_to_markdown
+    fn to_block(value: &Value) -> Block {
+        let block_type = value["type"].as_str().unwrap_or("").to_string();
+        let content = &value[&block_type];
+        let text = content["rich_text"]
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .map(|item| RichText {
+                        text: item["text"]["content"].as_str().unwrap_or("").to_string(),
+                        bold: item["annotations"]["bold"].as_bool().unwrap_or(false),
+                        italic: item["annotations"]["italic"].as_bool().unwrap_or(false),
+                        strikethrough: item["annotations"]["strikethrough"].as_bool().unwrap_or(false),
+                        code: item["annotations"]["code"].as_bool().unwrap_or(false),
+                        href: item["text"]["link"]["url"].as_str().map(|url| url.to_string()),
+                        ..Default::default()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Block {
+            id: String::new(),
+            block_type,
+            text,
+            checked: content["checked"].as_bool(),
+            language: content["language"].as_str().map(|language| language.to_string()),
+            icon: None,
+            url: None,
+            cells: Vec::new(),
+            children: content["children"]
+                .as_array()
+                .map(|children| children.iter().map(to_block).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn round_trip(markdown: &str) -> String {
+        let blocks: Vec<Block> = parse_markdown(markdown).iter().map(to_block).collect();
+        blocks_to_markdown(&blocks, &HashMap::new())
+    }
+
+    fn types(markdown: &str) -> Vec<String> {
+        parse_markdown(markdown)
+            .iter()
+            .map(|block| block["type"].as_str().unwrap_or("").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn headings() {
+        let markdown = "# One\n\n## Two\n\n### Three";
+        assert_eq!(types(markdown), ["heading_1", "heading_2", "heading_3"]);
+        assert_eq!(round_trip(markdown), markdown);
+        // notion has no fourth level
+        assert_eq!(round_trip("#### Four"), "### Four");
+    }
+
+    #[test]
+    fn lists() {
+        let markdown = "- first\n- second\n\n1. one\n2. two\n3. three";
+        assert_eq!(
+            types(markdown),
+            ["bulleted_list_item", "bulleted_list_item", "numbered_list_item", "numbered_list_item", "numbered_list_item"]
+        );
+        assert_eq!(round_trip(markdown), "- first\n- second\n1. one\n2. two\n3. three");
+        assert_eq!(parse_markdown(&round_trip(markdown)), parse_markdown(markdown));
+
+        // children sit after a blank line so a paragraph child isn't read as the item's own text
+        let nested = "- parent\n  - child\n    1. grandchild";
+        let blocks = parse_markdown(nested);
+        assert_eq!(blocks[0]["bulleted_list_item"]["children"][0]["type"], "bulleted_list_item");
+        assert_eq!(round_trip(nested), "- parent\n\n  - child\n\n    1. grandchild");
+        assert_eq!(parse_markdown(&round_trip(nested)), blocks);
+    }
+
+    #[test]
+    fn to_dos() {
+        let markdown = "- [ ] open\n- [x] done";
+        let blocks = parse_markdown(markdown);
+        assert_eq!(blocks[0]["type"], "to_do");
+        assert_eq!(blocks[0]["to_do"]["checked"], false);
+        assert_eq!(blocks[1]["to_do"]["checked"], true);
+        assert_eq!(round_trip(markdown), markdown);
+    }
+
+    #[test]
+    fn code_fences() {
+        let markdown = "```rust\nfn main() {\n    println!(\"*not emphasis*\");\n}\n```";
+        let blocks = parse_markdown(markdown);
+        assert_eq!(blocks[0]["code"]["language"], "rust");
+        assert_eq!(round_trip(markdown), markdown);
+        // short names map to the language notion knows, unknown ones to plain text
+        assert_eq!(parse_markdown("```py\nx\n```")[0]["code"]["language"], "python");
+        assert_eq!(round_trip("```brainfuck\n+\n```"), "```plain text\n+\n```");
+        // a fence inside the code gets a longer one around it
+        assert_eq!(round_trip("````\n```\n````"), "````plain text\n```\n````");
+    }
+
+    #[test]
+    fn quotes() {
+        let markdown = "> quoted **text**";
+        assert_eq!(types(markdown), ["quote"]);
+        assert_eq!(round_trip(markdown), markdown);
+    }
+
+    #[test]
+    fn inline_annotations() {
+        let markdown = "plain **bold** _italic_ ~~struck~~ `code` [link](<https://example.com>)";
+        assert_eq!(round_trip(markdown), markdown);
+
+        let rich_text = &parse_markdown("**bold**")[0]["paragraph"]["rich_text"];
+        assert_eq!(rich_text[0]["annotations"]["bold"], true);
+        assert_eq!(rich_text[0]["annotations"]["italic"], false);
+    }
+
+    #[test]
+    fn escapes() {
+        let markdown = "2 \\* 3 \\_ 4 \\[x\\]";
+        assert_eq!(plain_rich_text(&parse_markdown(markdown)[0]["paragraph"]["rich_text"]), "2 * 3 _ 4 [x]");
+        assert_eq!(round_trip(markdown), markdown);
+    }
+
+    // a paragraph holding text that would read as markdown syntax at the start of a line
+    fn paragraph(text: &str) -> Block {
+        to_block(&json!({ "type": "paragraph", "paragraph": { "rich_text": [{ "text": { "content": text } }] } }))
+    }
+
+    #[test]
+    fn line_start_markers() {
+        for text in ["# foo", "- foo", "+ foo", "* foo", "1. foo", "12) foo", "   > foo", "```", "---", "=", "a\n# b"] {
+            let markdown = blocks_to_markdown(&[paragraph(text)], &HashMap::new());
+            let blocks = parse_markdown(&markdown);
+            assert_eq!(blocks.len(), 1, "{:?} came back as {:?}", text, blocks);
+            assert_eq!(blocks[0]["type"], "paragraph", "{:?}", text);
+            // line breaks inside a paragraph come back as spaces
+            assert_eq!(plain_rich_text(&blocks[0]["paragraph"]["rich_text"]), text.replace('\n', " ").trim_start());
+            assert_eq!(parse_markdown(&round_trip(&markdown)), blocks);
+        }
+        assert_eq!(blocks_to_markdown(&[paragraph("# foo")], &HashMap::new()), "\\# foo");
+        assert_eq!(blocks_to_markdown(&[paragraph("1. foo")], &HashMap::new()), "1\\. foo");
+        // only the start of a line is touched
+        assert_eq!(blocks_to_markdown(&[paragraph("a - b # c")], &HashMap::new()), "a - b # c");
+    }
+
+    #[test]
+    fn relative_links_stay_text() {
+        let rich_text = &parse_markdown("[notes](notes.md)")[0]["paragraph"]["rich_text"];
+        assert_eq!(rich_text[0]["text"]["link"], Value::Null);
+    }
+
+    #[test]
+    fn leading_heading_is_the_title() {
+        let (title, blocks) = markdown_to_blocks("# Title\n\nbody");
+        assert_eq!(title.as_deref(), Some("Title"));
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(markdown_to_blocks("body\n\n# Later").0, None);
+    }
+}
@@ -1,4 +1,11 @@
 pub mod search;
 pub mod dimentions;
 pub mod controls;
-pub mod http;
\ No newline at end of file
+pub mod http;
+pub mod time;
+pub mod config;
+pub mod theme;
+pub mod keymap;
+pub mod markdown;
+pub mod edit;
+pub mod query;
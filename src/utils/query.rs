@@ -0,0 +1,534 @@
+use chrono::{DateTime, Days, Local, NaiveDate};
+use serde_json::{json, Value};
+
+use crate::api::{databases::Column, search::is_notion_id};
+
+// a parsed query, ready for /v1/databases/{id}/query. either part can be null
+pub struct Query {
+    pub filter: Value,
+    pub sorts: Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Op(&'static str),
+    Open,
+    Close,
+    // property name and whether it sorts descending
+    Sort(String, bool),
+}
+
+const OPERATORS: [&str; 8] = ["!=", "<=", ">=", "!~", "=", "<", ">", "~"];
+
+fn read_quoted(chars: &mut std::iter::Peekable<std::str::Chars>) -> Result<String, String> {
+    let quote = chars.next().unwrap_or('"');
+    let mut text = String::new();
+    loop {
+        match chars.next() {
+            Some('\\') => text.extend(chars.next()),
+            Some(c) if c == quote => return Ok(text),
+            Some(c) => text.push(c),
+            None => return Err(format!("missing closing {}", quote)),
+        }
+    }
+}
+
+// `sort:due`, `sort:-due`, `sort:due:desc` and `sort:"Due date"`
+fn sort_token(spec: &str) -> Token {
+    let (spec, descending) = match spec.strip_suffix(":desc") {
+        Some(spec) => (spec, true),
+        None => (spec.strip_suffix(":asc").unwrap_or(spec), false),
+    };
+    match spec.strip_prefix('-') {
+        Some(name) => Token::Sort(name.to_string(), true),
+        None => Token::Sort(spec.to_string(), descending),
+    }
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '(' || c == ')' {
+            chars.next();
+            tokens.push(if c == '(' { Token::Open } else { Token::Close });
+        } else if c == '"' || c == '\'' {
+            tokens.push(Token::Quoted(read_quoted(&mut chars)?));
+        } else if let Some(op) = OPERATORS.iter().find(|op| {
+            let mut ahead = chars.clone();
+            op.chars().all(|expected| ahead.next() == Some(expected))
+        }) {
+            for _ in 0..op.len() {
+                chars.next();
+            }
+            tokens.push(Token::Op(op));
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || "()=<>!~\"'".contains(c) {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            if word.eq_ignore_ascii_case("sort:") && matches!(chars.peek(), Some('"' | '\'')) {
+                tokens.push(Token::Sort(read_quoted(&mut chars)?, false));
+            } else if let Some(spec) = word.strip_prefix("sort:") {
+                tokens.push(sort_token(spec));
+            } else if word.is_empty() {
+                return Err(format!("unexpected \"{}\"", c));
+            } else {
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Word(word) => format!("\"{}\"", word),
+        Token::Quoted(text) => format!("\"{}\"", text),
+        Token::Op(op) => op.to_string(),
+        Token::Open => "(".to_string(),
+        Token::Close => ")".to_string(),
+        Token::Sort(name, _) => format!("sort:{}", name),
+    }
+}
+
+fn is_keyword(token: Option<&Token>, keyword: &str) -> bool {
+    matches!(token, Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+}
+
+fn find_column<'a>(columns: &'a [Column], name: &str) -> Result<&'a Column, String> {
+    columns
+        .iter()
+        .find(|column| column.name == name)
+        .or_else(|| columns.iter().find(|column| column.name.eq_ignore_ascii_case(name)))
+        .ok_or_else(|| format!("no property \"{}\"", name))
+}
+
+fn is_timestamp(column: &Column) -> bool {
+    column.property_type == "created_time" || column.property_type == "last_edited_time"
+}
+
+// today, tomorrow and yesterday, or an iso 8601 date
+fn date_value(raw: &str) -> Result<String, String> {
+    let today = Local::now().date_naive();
+    let date = match raw.to_lowercase().as_str() {
+        "today" => Some(today),
+        "tomorrow" => today.checked_add_days(Days::new(1)),
+        "yesterday" => today.checked_sub_days(Days::new(1)),
+        _ => None,
+    };
+    match date {
+        Some(date) => Ok(date.to_string()),
+        None if NaiveDate::parse_from_str(raw, "%Y-%m-%d").is_ok() || DateTime::parse_from_rfc3339(raw).is_ok() => {
+            Ok(raw.to_string())
+        }
+        None => Err(format!("\"{}\" is not a date", raw)),
+    }
+}
+
+// one `property op value` as a notion filter object
+fn condition(column: &Column, op: &str, raw: &str) -> Result<Value, String> {
+    let property_type = column.property_type.as_str();
+    let unsupported = || format!("{} can't be used on {} properties", op, property_type);
+
+    let (filter_type, operator, value) = match property_type {
+        "title" | "rich_text" | "url" | "email" | "phone_number" => {
+            let operator = match op {
+                "=" => "equals",
+                "!=" => "does_not_equal",
+                "~" => "contains",
+                "!~" => "does_not_contain",
+                _ => return Err(unsupported()),
+            };
+            (property_type, operator, json!(raw))
+        }
+        "number" => {
+            let operator = match op {
+                "=" => "equals",
+                "!=" => "does_not_equal",
+                "<" => "less_than",
+                ">" => "greater_than",
+                "<=" => "less_than_or_equal_to",
+                ">=" => "greater_than_or_equal_to",
+                _ => return Err(unsupported()),
+            };
+            let number: f64 = raw.parse().map_err(|_| format!("\"{}\" is not a number", raw))?;
+            ("number", operator, json!(number))
+        }
+        "select" | "status" => {
+            let operator = match op {
+                "=" => "equals",
+                "!=" => "does_not_equal",
+                _ => return Err(unsupported()),
+            };
+            // option names are matched ignoring case, notion's filter is exact
+            let name = column
+                .options
+                .iter()
+                .find(|option| option.eq_ignore_ascii_case(raw))
+                .map_or(raw, |option| option.as_str());
+            (property_type, operator, json!(name))
+        }
+        "multi_select" => {
+            let operator = match op {
+                "=" | "~" => "contains",
+                "!=" | "!~" => "does_not_contain",
+                _ => return Err(unsupported()),
+            };
+            let name = column
+                .options
+                .iter()
+                .find(|option| option.eq_ignore_ascii_case(raw))
+                .map_or(raw, |option| option.as_str());
+            ("multi_select", operator, json!(name))
+        }
+        "people" | "relation" => {
+            let operator = match op {
+                "=" | "~" => "contains",
+                "!=" | "!~" => "does_not_contain",
+                _ => return Err(unsupported()),
+            };
+            if !is_notion_id(raw) {
+                return Err(format!("{} filters need an id, \"{}\" isn't one", property_type, raw));
+            }
+            (property_type, operator, json!(raw))
+        }
+        "checkbox" => {
+            let checked = match raw.to_lowercase().as_str() {
+                "true" | "yes" | "x" => true,
+                "false" | "no" => false,
+                _ => return Err(format!("\"{}\" is not true or false", raw)),
+            };
+            let operator = match op {
+                "=" => "equals",
+                "!=" => "does_not_equal",
+                _ => return Err(unsupported()),
+            };
+            ("checkbox", operator, json!(checked))
+        }
+        "date" | "created_time" | "last_edited_time" => {
+            let operator = match op {
+                "=" => "equals",
+                "<" => "before",
+                ">" => "after",
+                "<=" => "on_or_before",
+                ">=" => "on_or_after",
+                _ => return Err(unsupported()),
+            };
+            (property_type, operator, json!(date_value(raw)?))
+        }
+        other => return Err(format!("{} properties can't be filtered", other)),
+    };
+
+    Ok(target(column, json!({ filter_type: { operator: value } })))
+}
+
+fn empty_condition(column: &Column, empty: bool) -> Result<Value, String> {
+    if column.property_type == "checkbox" {
+        return Err("checkboxes are never empty, compare them to true or false".to_string());
+    }
+    let operator = if empty { "is_empty" } else { "is_not_empty" };
+    Ok(target(column, json!({ column.property_type.clone(): { operator: true } })))
+}
+
+// point a condition at its property, timestamps are filtered by kind rather than name
+fn target(column: &Column, condition: Value) -> Value {
+    let mut filter = condition;
+    if is_timestamp(column) {
+        filter["timestamp"] = json!(column.property_type);
+    } else {
+        filter["property"] = json!(column.name);
+    }
+    filter
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    columns: &'a [Column],
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    // expr := all ("or" all)*
+    fn expr(&mut self) -> Result<Value, String> {
+        let mut any = vec![self.all()?];
+        while is_keyword(self.peek(), "or") {
+            self.next();
+            any.push(self.all()?);
+        }
+        Ok(if any.len() == 1 { any.remove(0) } else { json!({ "or": any }) })
+    }
+
+    // all := cond ("and" cond)*
+    fn all(&mut self) -> Result<Value, String> {
+        let mut all = vec![self.cond()?];
+        while is_keyword(self.peek(), "and") {
+            self.next();
+            all.push(self.cond()?);
+        }
+        Ok(if all.len() == 1 { all.remove(0) } else { json!({ "and": all }) })
+    }
+
+    // cond := "(" expr ")" | property op value | property "is" ["not"] "empty"
+    fn cond(&mut self) -> Result<Value, String> {
+        if self.peek() == Some(&Token::Open) {
+            self.next();
+            let inner = self.expr()?;
+            if self.next() != Some(Token::Close) {
+                return Err("missing closing )".to_string());
+            }
+            return Ok(inner);
+        }
+
+        // unquoted property names run until the operator
+        let mut name = Vec::new();
+        while let Some(token) = self.peek() {
+            match token {
+                Token::Quoted(text) if name.is_empty() => name.push(text.clone()),
+                Token::Word(_) if is_keyword(Some(token), "is") || is_keyword(Some(token), "contains") => break,
+                Token::Word(word) => name.push(word.clone()),
+                _ => break,
+            }
+            self.next();
+        }
+        if name.is_empty() {
+            return Err(match self.peek() {
+                Some(token) => format!("expected a property before {}", describe(token)),
+                None => "expected a property".to_string(),
+            });
+        }
+        let column = find_column(self.columns, &name.join(" "))?;
+
+        let op = match self.next() {
+            Some(Token::Op(op)) => op,
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("contains") => "~",
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("is") => {
+                let not = is_keyword(self.peek(), "not");
+                if not {
+                    self.next();
+                }
+                if !is_keyword(self.next().as_ref(), "empty") {
+                    return Err(format!("expected \"empty\" after \"{} is\"", column.name));
+                }
+                return empty_condition(column, !not);
+            }
+            _ => return Err(format!("expected an operator after \"{}\"", column.name)),
+        };
+
+        match self.next() {
+            Some(Token::Word(value) | Token::Quoted(value)) => condition(column, op, &value),
+            _ => Err(format!("expected a value after \"{} {}\"", column.name, op)),
+        }
+    }
+}
+
+// `status = "Doing" and due < today sort:due`, checked against the database's columns
+pub fn parse_query(query: &str, columns: &[Column]) -> Result<Query, String> {
+    let (sorts, tokens): (Vec<Token>, Vec<Token>) = tokenize(query)?
+        .into_iter()
+        .partition(|token| matches!(token, Token::Sort(..)));
+
+    let sorts = sorts
+        .into_iter()
+        .map(|token| {
+            let Token::Sort(name, descending) = token else { unreachable!() };
+            let column = find_column(columns, &name)?;
+            let direction = if descending { "descending" } else { "ascending" };
+            Ok(if is_timestamp(column) {
+                json!({ "timestamp": column.property_type, "direction": direction })
+            } else {
+                json!({ "property": column.name, "direction": direction })
+            })
+        })
+        .collect::<Result<Vec<Value>, String>>()?;
+
+    let filter = if tokens.is_empty() {
+        Value::Null
+    } else {
+        let mut parser = Parser { tokens, pos: 0, columns };
+        let filter = parser.expr()?;
+        if let Some(token) = parser.peek() {
+            return Err(format!("unexpected {}, join conditions with and / or", describe(token)));
+        }
+        filter
+    };
+
+    Ok(Query {
+        filter,
+        sorts: if sorts.is_empty() { Value::Null } else { json!(sorts) },
+    })
+}
+
+// a saved query with more words after it. both sides are bracketed so an `or` on
+// either one can't swallow the other, unless one side is only sorts
+pub fn narrow(query: &str, extra: &str) -> String {
+    let has_conditions =
+        |text: &str| tokenize(text).map_or(true, |tokens| tokens.iter().any(|token| !matches!(token, Token::Sort(..))));
+    if has_conditions(query) && has_conditions(extra) {
+        format!("({}) and ({})", query, extra)
+    } else {
+        format!("{} {}", query, extra).trim().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, property_type: &str, options: &[&str]) -> Column {
+        Column {
+            name: name.to_string(),
+            property_type: property_type.to_string(),
+            options: options.iter().map(|option| option.to_string()).collect(),
+            done: Vec::new(),
+        }
+    }
+
+    fn columns() -> Vec<Column> {
+        vec![
+            column("Name", "title", &[]),
+            column("Status", "status", &["Todo", "Doing", "Done"]),
+            column("Due date", "date", &[]),
+            column("Points", "number", &[]),
+            column("Tags", "multi_select", &["Work", "Home"]),
+            column("Billable", "checkbox", &[]),
+            column("Edited", "last_edited_time", &[]),
+        ]
+    }
+
+    fn filter(query: &str) -> Value {
+        parse_query(query, &columns()).unwrap().filter
+    }
+
+    fn error(query: &str) -> String {
+        parse_query(query, &columns()).err().unwrap()
+    }
+
+    #[test]
+    fn single_condition() {
+        assert_eq!(filter("points >= 3"), json!({ "property": "Points", "number": { "greater_than_or_equal_to": 3.0 } }));
+        // option names are matched ignoring case
+        assert_eq!(filter("status = doing"), json!({ "property": "Status", "status": { "equals": "Doing" } }));
+        assert_eq!(filter("tags ~ work"), json!({ "property": "Tags", "multi_select": { "contains": "Work" } }));
+        assert_eq!(filter("billable = yes"), json!({ "property": "Billable", "checkbox": { "equals": true } }));
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        let a = json!({ "property": "Points", "number": { "equals": 1.0 } });
+        let b = json!({ "property": "Points", "number": { "equals": 2.0 } });
+        let c = json!({ "property": "Points", "number": { "equals": 3.0 } });
+        assert_eq!(
+            filter("points = 1 or points = 2 and points = 3"),
+            json!({ "or": [a, { "and": [b, c] }] })
+        );
+        assert_eq!(
+            filter("(points = 1 or points = 2) and points = 3"),
+            json!({ "and": [{ "or": [a, b] }, c] })
+        );
+        assert_eq!(filter("points = 1 OR points = 2"), json!({ "or": [a, b] }));
+    }
+
+    #[test]
+    fn quoting() {
+        let due = json!({ "property": "Due date", "date": { "equals": "2024-05-01" } });
+        assert_eq!(filter("\"Due date\" = 2024-05-01"), due);
+        // unquoted names run up to the operator
+        assert_eq!(filter("due date = 2024-05-01"), due);
+        assert_eq!(
+            filter("name ~ 'say \"hi\" and \\'bye\\''"),
+            json!({ "property": "Name", "title": { "contains": "say \"hi\" and 'bye'" } })
+        );
+        assert_eq!(error("name = \"open"), "missing closing \"");
+    }
+
+    #[test]
+    fn empty_checks() {
+        assert_eq!(filter("tags is empty"), json!({ "property": "Tags", "multi_select": { "is_empty": true } }));
+        assert_eq!(filter("tags is not empty"), json!({ "property": "Tags", "multi_select": { "is_not_empty": true } }));
+        assert_eq!(error("billable is empty"), "checkboxes are never empty, compare them to true or false");
+        assert_eq!(error("tags is full"), "expected \"empty\" after \"Tags is\"");
+    }
+
+    #[test]
+    fn timestamps_filter_by_kind() {
+        assert_eq!(
+            filter("edited > 2024-01-01"),
+            json!({ "timestamp": "last_edited_time", "last_edited_time": { "after": "2024-01-01" } })
+        );
+    }
+
+    #[test]
+    fn sorts() {
+        let query = parse_query("sort:points sort:-edited sort:\"Due date\" sort:status:desc", &columns()).unwrap();
+        assert_eq!(query.filter, Value::Null);
+        assert_eq!(
+            query.sorts,
+            json!([
+                { "property": "Points", "direction": "ascending" },
+                { "timestamp": "last_edited_time", "direction": "descending" },
+                { "property": "Due date", "direction": "ascending" },
+                { "property": "Status", "direction": "descending" },
+            ])
+        );
+        // sorts can sit anywhere among the conditions
+        let query = parse_query("points > 1 sort:points and points < 5", &columns()).unwrap();
+        assert!(query.filter["and"].is_array());
+        assert_eq!(query.sorts[0]["property"], "Points");
+        assert_eq!(parse_query("", &columns()).unwrap().sorts, Value::Null);
+    }
+
+    #[test]
+    fn narrowing() {
+        let a = json!({ "property": "Points", "number": { "equals": 1.0 } });
+        let b = json!({ "property": "Points", "number": { "equals": 2.0 } });
+        let c = json!({ "property": "Points", "number": { "equals": 3.0 } });
+        assert_eq!(
+            filter(&narrow("points = 1", "points = 2 or points = 3")),
+            json!({ "and": [a, { "or": [b, c] }] })
+        );
+        assert_eq!(
+            filter(&narrow("points = 1 or points = 2", "points = 3")),
+            json!({ "and": [{ "or": [a, b] }, c] })
+        );
+
+        // a side with only sorts is added as it is
+        let query = parse_query(&narrow("sort:points", "points = 1"), &columns()).unwrap();
+        assert_eq!((query.filter, query.sorts[0]["property"].clone()), (a.clone(), json!("Points")));
+        let query = parse_query(&narrow("points = 1", "sort:-points"), &columns()).unwrap();
+        assert_eq!((query.filter, query.sorts[0]["direction"].clone()), (a, json!("descending")));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(error("owner = me"), "no property \"owner\"");
+        assert_eq!(error("sort:owner"), "no property \"owner\"");
+        assert_eq!(error("points = many"), "\"many\" is not a number");
+        assert_eq!(error("points ~ 3"), "~ can't be used on number properties");
+        assert_eq!(error("due date < someday"), "\"someday\" is not a date");
+        assert_eq!(error("points = 1 points = 2"), "unexpected \"points\", join conditions with and / or");
+        assert_eq!(error("(points = 1"), "missing closing )");
+        assert_eq!(error("points"), "expected an operator after \"Points\"");
+        assert_eq!(error("points ="), "expected a value after \"Points =\"");
+        assert_eq!(error("= 3"), "expected a property before =");
+    }
+}
@@ -1,46 +1,40 @@
-use std::collections::HashMap;
+use std::error::Error;
 
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
+use indexmap::IndexMap;
 
 use crate::api::search::Object;
 
 // fuzzy search query in given titles
 pub fn match_search(
     query: &str,
-    options: &Result<HashMap<String, Object>, reqwest::Error>,
-) -> HashMap<String, Object> {
-    let mut titles = Vec::<String>::new();
-    let objects;
-    match options {
-        Ok(options) => {
-            for (title, _) in options.clone() {
-                titles.push(title);
-            }
-            objects = options;
-        }
-        Err(_) => return HashMap::new(),
-    }
+    options: &Result<IndexMap<String, Object>, Box<dyn Error>>,
+) -> IndexMap<String, Object> {
+    let objects = match options {
+        Ok(options) => options,
+        Err(_) => return IndexMap::new(),
+    };
 
     if query.is_empty() {
         return objects.clone();
     }
 
     let matcher = SkimMatcherV2::default();
-    let mut results: Vec<(&str, i64)> = titles
+    let mut results: Vec<(&String, i64)> = objects
         .iter()
-        .filter_map(|title| {
+        .filter_map(|(id, object)| {
             matcher
-                .fuzzy_match(&title.as_str(), query)
-                .map(|score| (title.as_str(), score))
+                .fuzzy_match(object.title.as_str(), query)
+                .map(|score| (id, score))
         })
         .collect();
 
     results.sort_by_key(|&(_, score)| -score);
-    let mut sorted_options = HashMap::<String, Object>::new();
+    let mut sorted_options = IndexMap::<String, Object>::new();
 
-    for title in results.iter().map(|(title, _)| title.to_string()) {
-        sorted_options.insert(title.clone(), objects.get(&title).unwrap().clone());
+    for (id, _) in results {
+        sorted_options.insert(id.clone(), objects.get(id).unwrap().clone());
     }
 
     sorted_options
@@ -0,0 +1,54 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crossterm::style::Color;
+
+use crate::utils::config::get_config;
+
+pub const THEMES: [&str; 3] = ["default", "rose", "mono"];
+
+const UNSET: usize = usize::MAX;
+
+// index into THEMES, so drawing a cell doesn't lock and clone the whole config
+static THEME: AtomicUsize = AtomicUsize::new(UNSET);
+
+fn theme() -> &'static str {
+    let mut index = THEME.load(Ordering::Relaxed);
+    if index == UNSET {
+        let name = get_config().theme;
+        index = THEMES.iter().position(|theme| *theme == name).unwrap_or(0);
+        THEME.store(index, Ordering::Relaxed);
+    }
+    THEMES[index]
+}
+
+// look the theme up again after the config changed
+pub fn reset_theme() {
+    THEME.store(UNSET, Ordering::Relaxed);
+}
+
+// highlight behind the selected row
+pub fn accent_color() -> Color {
+    match theme() {
+        "rose" => Color::Rgb { r: 180, g: 99, b: 122 },
+        "mono" => Color::Grey,
+        _ => Color::Rgb { r: 49, g: 116, b: 143 },
+    }
+}
+
+// the favorite marker
+pub fn favorite_color() -> Color {
+    match theme() {
+        "rose" => Color::Rgb { r: 246, g: 193, b: 119 },
+        "mono" => Color::White,
+        _ => Color::Rgb { r: 252, g: 186, b: 3 },
+    }
+}
+
+// highlight for the pages column that isn't focused
+pub fn secondary_color() -> Color {
+    match theme() {
+        "rose" => Color::Rgb { r: 144, g: 122, b: 169 },
+        "mono" => Color::DarkGrey,
+        _ => Color::Rgb { r: 66, g: 36, b: 156 },
+    }
+}
@@ -0,0 +1,13 @@
+use chrono::Utc;
+
+// short human readable age of a unix timestamp
+pub fn relative_time(timestamp: i64) -> String {
+    let seconds = (Utc::now().timestamp() - timestamp).max(0);
+
+    match seconds {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{}m ago", seconds / 60),
+        3600..=86399 => format!("{}h ago", seconds / 3600),
+        _ => format!("{}d ago", seconds / 86400),
+    }
+}
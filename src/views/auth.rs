@@ -7,7 +7,25 @@ use crossterm::{
     style::{Color, Print, SetForegroundColor},
 };
 
-use crate::utils::http::{initialize_server, start_server};
+use crate::{
+    app::Workspace,
+    utils::http::{initialize_server, start_server},
+    views::{Layout, View},
+};
+
+pub struct AuthView;
+
+impl View for AuthView {
+    fn render(&self, layout: &Layout, _workspace: &Workspace) {
+        auth_view(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_center,
+            &layout.y_search,
+        );
+    }
+}
 
 
 pub fn auth_view(
@@ -32,13 +50,13 @@ pub fn auth_view(
     initialize_server();
     start_server();
 
-    if let Err(e) = webbrowser::open(&auth_url) {
+    if let Err(e) = webbrowser::open(auth_url) {
         eprintln!("Failed to open browser: {}", e);
     }
 
     execute!(
         stdout(),
-        MoveTo(x_center - (text.len() as u16 / 2), y_center - 0),
+        MoveTo(x_center - (text.len() as u16 / 2), *y_center),
         SetForegroundColor(Color::White),
         Print(text),
         MoveTo(x_center - (auth_url.len() as u16 / 2), y_center + 8),
@@ -0,0 +1,387 @@
+use std::io::stdout;
+
+use crossterm::{
+    cursor::MoveTo,
+    execute,
+    style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor, Stylize},
+};
+
+use crossterm::event::KeyEvent;
+use serde_json::{json, Value};
+
+use crate::{
+    api::{
+        databases::{empty_property, Column, Row},
+        search::Object,
+    },
+    app::{Section, Workspace},
+    utils::{controls::write_ctrl, keymap::Action, theme::accent_color},
+    views::{grid::fit, Command, Layout, View},
+};
+
+const MIN_LANE_WIDTH: usize = 20;
+
+// one option of the grouping property and the rows set to it
+struct Lane {
+    name: String,
+    // none for rows with the property left empty
+    option: Option<String>,
+    cards: Vec<usize>,
+}
+
+// a database's rows as cards in lanes, one per option of a select or status property
+pub struct Board {
+    pub database: Object,
+    pub columns: Vec<Column>,
+    pub rows: Vec<Row>,
+    pub titles: Vec<String>,
+    // index into `columns` of the property the lanes come from
+    group: usize,
+    lanes: Vec<Lane>,
+    pub lane_pos: usize,
+    pub card_pos: usize,
+    // a card shown moved before notion confirmed it, with the lane it came from
+    pending: Option<(usize, usize)>,
+    pub status: Option<String>,
+    pub return_section: Section,
+}
+
+// the columns a board can be grouped by
+pub fn groupable(columns: &[Column]) -> Vec<usize> {
+    columns
+        .iter()
+        .enumerate()
+        .filter(|(_, column)| matches!(column.property_type.as_str(), "select" | "status"))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+impl Board {
+    // none when the database has nothing to group by
+    pub fn new(
+        database: Object,
+        columns: Vec<Column>,
+        rows: Vec<Row>,
+        titles: Vec<String>,
+        return_section: Section,
+    ) -> Option<Self> {
+        let group = *groupable(&columns).first()?;
+        let mut board = Self {
+            database,
+            columns,
+            rows,
+            titles,
+            group,
+            lanes: Vec::new(),
+            lane_pos: 0,
+            card_pos: 0,
+            pending: None,
+            status: None,
+            return_section,
+        };
+        board.arrange();
+        Some(board)
+    }
+
+    fn group_column(&self) -> &Column {
+        &self.columns[self.group]
+    }
+
+    fn option_of(&self, row: usize) -> Option<String> {
+        let column = self.group_column();
+        self.rows[row].properties[&column.name][&column.property_type]["name"]
+            .as_str()
+            .map(|name| name.to_string())
+    }
+
+    // sort every row into the lane for its option, in the order notion lists the options
+    fn arrange(&mut self) {
+        let column = self.group_column();
+        let mut lanes: Vec<Lane> = std::iter::once(Lane {
+            name: format!("no {}", column.name.to_lowercase()),
+            option: None,
+            cards: Vec::new(),
+        })
+        .chain(column.options.iter().map(|option| Lane {
+            name: option.clone(),
+            option: Some(option.clone()),
+            cards: Vec::new(),
+        }))
+        .collect();
+
+        for row in 0..self.rows.len() {
+            let option = self.option_of(row);
+            match lanes.iter_mut().find(|lane| lane.option == option) {
+                Some(lane) => lane.cards.push(row),
+                // an option added since the columns were fetched
+                None => lanes.push(Lane {
+                    name: option.clone().unwrap_or_default(),
+                    option,
+                    cards: vec![row],
+                }),
+            }
+        }
+
+        // statuses can't be empty, so only selects keep the lane for unset rows
+        if column.property_type == "status" && lanes[0].cards.is_empty() {
+            lanes.remove(0);
+        }
+        self.lanes = lanes;
+        self.lane_pos = self.lane_pos.min(self.lanes.len().saturating_sub(1));
+        self.clamp_card();
+    }
+
+    fn clamp_card(&mut self) {
+        let count = self.lanes.get(self.lane_pos).map_or(0, |lane| lane.cards.len());
+        self.card_pos = self.card_pos.min(count.saturating_sub(1));
+    }
+
+    fn selected_card(&self) -> Option<usize> {
+        self.lanes.get(self.lane_pos)?.cards.get(self.card_pos).copied()
+    }
+
+    pub fn selected_object(&self) -> Option<Object> {
+        self.selected_card().map(|row| Object {
+            id: self.rows[row].id.clone(),
+            parent_id: Some(self.database.id.clone()),
+            title: self.titles[row].clone(),
+            object_type: "page".to_string(),
+            children: Vec::new(),
+            last_edited_time: None,
+        })
+    }
+
+    fn next_group(&mut self) {
+        let groups = groupable(&self.columns);
+        let index = groups.iter().position(|&group| group == self.group).unwrap_or(0);
+        self.group = groups[(index + 1) % groups.len()];
+        self.lane_pos = 0;
+        self.card_pos = 0;
+        self.arrange();
+        self.status = Some(format!("grouped by {}", self.group_column().name));
+    }
+
+    // take the card out of its lane and put it in another, keeping the database's order
+    fn place(&mut self, row: usize, from: usize, to: usize) {
+        self.lanes[from].cards.retain(|&card| card != row);
+        let cards = &mut self.lanes[to].cards;
+        let index = cards.iter().position(|&card| card > row).unwrap_or(cards.len());
+        cards.insert(index, row);
+        self.lane_pos = to;
+        self.card_pos = index;
+    }
+
+    // move the selected card to the next or previous lane, ahead of notion saving it
+    fn move_card(&mut self, delta: isize) -> Command {
+        if self.pending.is_some() {
+            self.status = Some("still saving the last move".to_string());
+            return Command::None;
+        }
+        let Some(row) = self.selected_card() else {
+            return Command::None;
+        };
+        let Some(to) = self
+            .lane_pos
+            .checked_add_signed(delta)
+            .filter(|&to| to < self.lanes.len())
+        else {
+            return Command::None;
+        };
+
+        let column = self.group_column().clone();
+        let value = match &self.lanes[to].option {
+            Some(option) => json!({ column.property_type.clone(): { "name": option } }),
+            None if column.property_type == "status" => {
+                self.status = Some(format!("{} can't be empty", column.name));
+                return Command::None;
+            }
+            None => empty_property(&column),
+        };
+
+        let from = self.lane_pos;
+        self.place(row, from, to);
+        self.pending = Some((row, from));
+        self.status = Some("saving...".to_string());
+        Command::SetProperty(self.rows[row].id.clone(), column.name, value)
+    }
+
+    // notion accepted the move, take its version of the row
+    pub fn confirm(&mut self, id: &str, properties: Value) {
+        if let Some(row) = self.rows.iter().position(|row| row.id == id) {
+            self.rows[row].properties = properties;
+        }
+        if self.pending.take().is_some() {
+            self.status = Some(format!("moved to {}", self.lanes[self.lane_pos].name));
+        }
+    }
+
+    // notion refused the move, put the card back
+    pub fn rollback(&mut self, error: String) {
+        if let Some((row, from)) = self.pending.take() {
+            if let Some(to) = self.lanes.iter().position(|lane| lane.cards.contains(&row)) {
+                self.place(row, to, from);
+            }
+            self.status = Some(format!("not moved, {}", error));
+        }
+    }
+}
+
+impl View for Board {
+    fn update(&mut self, action: Action, _key: &KeyEvent, _workspace: &mut Workspace) -> Command {
+        self.status = None;
+        match action {
+            Action::Left | Action::Right => {
+                let delta = if action == Action::Left { -1 } else { 1 };
+                self.lane_pos = self
+                    .lane_pos
+                    .saturating_add_signed(delta)
+                    .min(self.lanes.len().saturating_sub(1));
+                self.clamp_card();
+            }
+            Action::Up => self.card_pos = self.card_pos.saturating_sub(1),
+            Action::Down => {
+                self.card_pos += 1;
+                self.clamp_card();
+            }
+            Action::MoveLeft => return self.move_card(-1),
+            Action::MoveRight => return self.move_card(1),
+            Action::Group => self.next_group(),
+            Action::Open => return self.selected_object().map_or(Command::None, Command::Open),
+            Action::Back => return Command::Goto(self.return_section),
+            _ => return Command::Unhandled,
+        }
+        Command::None
+    }
+
+    fn render(&self, layout: &Layout, _workspace: &Workspace) {
+        board_view(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_search,
+            self,
+        );
+    }
+}
+
+pub fn board_view(
+    content_width: &u16,
+    content_height: &u16,
+    x_center: &u16,
+    y_search: &u16,
+    board: &Board,
+) {
+    for i in 2..content_height - 4 {
+        execute!(
+            stdout(),
+            MoveTo(x_center - (content_width / 2) + 1, y_search + i + 1),
+            Print(" ".repeat((content_width - 2) as usize)),
+        )
+        .unwrap();
+    }
+
+    let width = content_width.saturating_sub(6) as usize;
+    let x_board = x_center - (content_width / 2) + 3;
+
+    let title: String = format!("{} by {}", board.database.title, board.group_column().name)
+        .chars()
+        .take(width.saturating_sub(2))
+        .collect();
+    execute!(
+        stdout(),
+        MoveTo(x_board, y_search + 4),
+        SetBackgroundColor(Color::White),
+        SetForegroundColor(Color::Black),
+        Print(format!(" {} ", title)),
+        ResetColor
+    )
+    .unwrap();
+
+    if let Some(status) = &board.status {
+        let status: String = status.chars().take(width.saturating_sub(title.chars().count() + 4)).collect();
+        execute!(
+            stdout(),
+            MoveTo(x_board + width as u16 - status.chars().count() as u16, y_search + 4),
+            SetForegroundColor(Color::DarkGrey),
+            Print(status),
+            ResetColor
+        )
+        .unwrap();
+    }
+
+    let hint = "[shift+←/→] move  [g] group  [enter] open";
+    write_ctrl(
+        hint,
+        x_board + (width as u16).saturating_sub(hint.chars().count() as u16),
+        y_search + content_height - 5,
+    );
+
+    // as many lanes as fit side by side, paging to keep the selected one in view
+    let shown = ((width + 2) / (MIN_LANE_WIDTH + 2)).clamp(1, board.lanes.len().max(1));
+    let lane_width = (width + 2) / shown - 2;
+    let first = shown * (board.lane_pos / shown);
+    let count = (*content_height as usize).saturating_sub(14).max(1);
+
+    for (column, (i, lane)) in board.lanes.iter().enumerate().skip(first).take(shown).enumerate() {
+        let x = x_board + (column * (lane_width + 2)) as u16;
+        let current = i == board.lane_pos;
+        let header = fit(&format!("{} ({})", lane.name, lane.cards.len()), lane_width);
+        execute!(
+            stdout(),
+            MoveTo(x, y_search + 6),
+            SetForegroundColor(if current { Color::White } else { Color::Grey }),
+            Print(header.bold()),
+            MoveTo(x, y_search + 7),
+            SetForegroundColor(if current { accent_color() } else { Color::DarkGrey }),
+            Print("─".repeat(lane_width)),
+            ResetColor
+        )
+        .unwrap();
+
+        let start = if current { count * (board.card_pos / count) } else { 0 };
+        for (j, &row) in lane.cards.iter().enumerate().skip(start).take(count) {
+            let selected = current && j == board.card_pos;
+            execute!(
+                stdout(),
+                MoveTo(x, y_search + 8 + (j - start) as u16),
+                SetForegroundColor(if selected { Color::White } else { Color::Grey }),
+                SetBackgroundColor(if selected { accent_color() } else { Color::Reset }),
+                Print(fit(&format!(" {}", board.titles[row]), lane_width)),
+                ResetColor
+            )
+            .unwrap();
+        }
+        if lane.cards.len() > start + count {
+            execute!(
+                stdout(),
+                MoveTo(x, y_search + 8 + count as u16),
+                SetForegroundColor(Color::DarkGrey),
+                Print(format!(" +{} more", lane.cards.len() - start - count)),
+                ResetColor
+            )
+            .unwrap();
+        }
+    }
+
+    // lane scroll markers
+    if first > 0 {
+        execute!(
+            stdout(),
+            MoveTo(x_board - 2, y_search + 6),
+            SetForegroundColor(Color::DarkGrey),
+            Print("←"),
+            ResetColor
+        )
+        .unwrap();
+    }
+    if first + shown < board.lanes.len() {
+        execute!(
+            stdout(),
+            MoveTo(x_center + (content_width / 2) - 2, y_search + 6),
+            SetForegroundColor(Color::DarkGrey),
+            Print("→"),
+            ResetColor
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,573 @@
+use std::io::stdout;
+
+use chrono::{DateTime, Datelike, Duration, Local, Months, NaiveDate, NaiveDateTime, NaiveTime};
+use crossterm::{
+    cursor::MoveTo,
+    execute,
+    style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor, Stylize},
+};
+
+use crossterm::event::KeyEvent;
+use serde_json::{json, Value};
+
+use crate::{
+    api::{
+        databases::{property_text, query_database_filtered, Column},
+        search::Object,
+    },
+    app::{Section, Workspace},
+    utils::{controls::write_ctrl, keymap::Action, theme::accent_color},
+    views::{grid::fit, Command, Layout, View},
+};
+
+const AGENDA_DAYS: i64 = 28;
+// rows are fetched by start date, so ranges that began longer ago than this are missed
+const LOOKBACK_DAYS: i64 = 28;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Mode {
+    Agenda,
+    Month,
+}
+
+// a row placed on the days its date property covers
+pub struct Entry {
+    pub id: String,
+    pub title: String,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    // none for all-day dates
+    pub start_time: Option<NaiveTime>,
+    pub end_time: Option<NaiveTime>,
+}
+
+impl Entry {
+    // from a date property's value, an end before the start is dropped
+    fn new(id: String, title: String, date: &Value) -> Option<Self> {
+        let (start, start_time) = parse_moment(date["start"].as_str()?)?;
+        let (end, end_time) = date["end"]
+            .as_str()
+            .and_then(parse_moment)
+            .filter(|(end, _)| *end >= start)
+            .unwrap_or((start, None));
+        Some(Entry {
+            id,
+            title,
+            start,
+            end,
+            start_time,
+            end_time,
+        })
+    }
+
+    // how the entry reads on one of its days
+    fn label_on(&self, day: NaiveDate) -> String {
+        let days = (self.end - self.start).num_days() + 1;
+        match self.start_time {
+            None if days == 1 => "all day".to_string(),
+            None => format!("day {}/{}", (day - self.start).num_days() + 1, days),
+            Some(start) if days == 1 => match self.end_time {
+                Some(end) => format!("{}–{}", start.format("%H:%M"), end.format("%H:%M")),
+                None => start.format("%H:%M").to_string(),
+            },
+            Some(start) if day == self.start => format!("from {}", start.format("%H:%M")),
+            Some(_) if day == self.end => self
+                .end_time
+                .map_or("all day".to_string(), |end| format!("until {}", end.format("%H:%M"))),
+            Some(_) => "all day".to_string(),
+        }
+    }
+}
+
+// a date or date-time as notion writes it, in local time when it has one
+fn parse_moment(text: &str) -> Option<(NaiveDate, Option<NaiveTime>)> {
+    if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        return Some((date, None));
+    }
+    if let Ok(moment) = DateTime::parse_from_rfc3339(text) {
+        let local = moment.with_timezone(&Local);
+        return Some((local.date_naive(), Some(local.time())));
+    }
+    NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S%.f")
+        .ok()
+        .map(|moment| (moment.date(), Some(moment.time())))
+}
+
+// the database's rows by one of its date properties, as an agenda or a month grid
+pub struct Calendar {
+    pub database: Object,
+    pub columns: Vec<Column>,
+    // index into `columns` of the date property in use
+    date: usize,
+    pub mode: Mode,
+    // the selected day in the month, or the first day of the agenda
+    pub day: NaiveDate,
+    pub entries: Vec<Entry>,
+    // the agenda item selected
+    pub pos: usize,
+    // days every entry is known for
+    loaded: Option<(NaiveDate, NaiveDate)>,
+    pub message: Option<String>,
+    pub status: Option<String>,
+    pub return_section: Section,
+}
+
+// the columns a calendar can be laid out by
+pub fn date_columns(columns: &[Column]) -> Vec<usize> {
+    columns
+        .iter()
+        .enumerate()
+        .filter(|(_, column)| column.property_type == "date")
+        .map(|(i, _)| i)
+        .collect()
+}
+
+impl Calendar {
+    // none when the database has no date property
+    pub fn new(database: Object, columns: Vec<Column>, return_section: Section) -> Option<Self> {
+        let date = *date_columns(&columns).first()?;
+        Some(Self {
+            database,
+            columns,
+            date,
+            mode: Mode::Agenda,
+            day: Local::now().date_naive(),
+            entries: Vec::new(),
+            pos: 0,
+            loaded: None,
+            message: Some("loading...".to_string()),
+            status: None,
+            return_section,
+        })
+    }
+
+    fn date_column(&self) -> &Column {
+        &self.columns[self.date]
+    }
+
+    // monday of the week the month starts in, the grid always shows six weeks from it
+    fn grid_start(&self) -> NaiveDate {
+        let first = self.day.with_day(1).unwrap_or(self.day);
+        first - Duration::days(first.weekday().num_days_from_monday() as i64)
+    }
+
+    // the days the current mode shows
+    fn shown(&self) -> (NaiveDate, NaiveDate) {
+        match self.mode {
+            Mode::Agenda => (self.day, self.day + Duration::days(AGENDA_DAYS - 1)),
+            Mode::Month => (self.grid_start(), self.grid_start() + Duration::days(41)),
+        }
+    }
+
+    fn needs_fetch(&self) -> bool {
+        let (from, to) = self.shown();
+        !self.loaded.is_some_and(|(start, end)| start <= from && to <= end)
+    }
+
+    // the rows dated around what is shown, a month either side so paging rarely refetches
+    pub async fn fetch(&mut self) {
+        let (from, to) = self.shown();
+        let (from, to) = (from - Duration::days(LOOKBACK_DAYS), to + Duration::days(LOOKBACK_DAYS));
+        let column = self.date_column().name.clone();
+        let filter = json!({ "and": [
+            { "property": column, "date": { "on_or_after": from.to_string() } },
+            { "property": column, "date": { "on_or_before": to.to_string() } },
+        ]});
+        let sorts = json!([{ "property": column, "direction": "ascending" }]);
+
+        match query_database_filtered(&self.database.id, filter, sorts).await {
+            Ok(rows) => {
+                let title = self.columns.first().map(|column| column.name.clone()).unwrap_or_default();
+                self.entries = rows
+                    .iter()
+                    .filter_map(|row| {
+                        Entry::new(
+                            row.id.clone(),
+                            property_text(&row.properties[&title]),
+                            &row.properties[&column]["date"],
+                        )
+                    })
+                    .collect();
+                self.loaded = Some((from, to));
+                self.message = None;
+                self.pos = self.pos.min(self.agenda().len().saturating_sub(1));
+            }
+            Err(e) => self.message = Some(format!("failed to load dates: {}", e)),
+        }
+    }
+
+    // entries on a day, all-day ones first and the rest by time
+    fn entries_on(&self, day: NaiveDate) -> Vec<usize> {
+        let mut entries: Vec<usize> = (0..self.entries.len())
+            .filter(|&i| self.entries[i].start <= day && day <= self.entries[i].end)
+            .collect();
+        entries.sort_by_key(|&i| {
+            let entry = &self.entries[i];
+            let time = if entry.start == day { entry.start_time } else { None };
+            (time.is_some(), time)
+        });
+        entries
+    }
+
+    // every agenda item as its day and entry, in the order they are listed
+    fn agenda(&self) -> Vec<(NaiveDate, usize)> {
+        (0..AGENDA_DAYS)
+            .map(|offset| self.day + Duration::days(offset))
+            .flat_map(|day| self.entries_on(day).into_iter().map(move |entry| (day, entry)))
+            .collect()
+    }
+
+    pub fn selected_object(&self) -> Option<Object> {
+        let (_, entry) = *self.agenda().get(self.pos)?;
+        let entry = &self.entries[entry];
+        Some(Object {
+            id: entry.id.clone(),
+            parent_id: Some(self.database.id.clone()),
+            title: entry.title.clone(),
+            object_type: "page".to_string(),
+            children: Vec::new(),
+            last_edited_time: None,
+        })
+    }
+
+    // move the day, asking for rows when it leaves what was fetched
+    fn go_to(&mut self, day: NaiveDate) -> Command {
+        self.day = day;
+        self.pos = 0;
+        if self.needs_fetch() {
+            self.message = Some("loading...".to_string());
+            return Command::Refresh;
+        }
+        Command::None
+    }
+
+    fn add_months(&self, months: i32) -> NaiveDate {
+        let shifted = if months < 0 {
+            self.day.checked_sub_months(Months::new(months.unsigned_abs()))
+        } else {
+            self.day.checked_add_months(Months::new(months as u32))
+        };
+        shifted.unwrap_or(self.day)
+    }
+
+    fn next_date(&mut self) -> Command {
+        let dates = date_columns(&self.columns);
+        let index = dates.iter().position(|&date| date == self.date).unwrap_or(0);
+        self.date = dates[(index + 1) % dates.len()];
+        self.loaded = None;
+        self.status = Some(format!("by {}", self.date_column().name));
+        self.go_to(self.day)
+    }
+}
+
+impl View for Calendar {
+    fn update(&mut self, action: Action, _key: &KeyEvent, _workspace: &mut Workspace) -> Command {
+        self.status = None;
+        match (self.mode, action) {
+            (Mode::Agenda, Action::Up) => self.pos = self.pos.saturating_sub(1),
+            (Mode::Agenda, Action::Down) => {
+                self.pos = (self.pos + 1).min(self.agenda().len().saturating_sub(1));
+            }
+            (Mode::Agenda, Action::Left) => return self.go_to(self.day - Duration::days(7)),
+            (Mode::Agenda, Action::Right) => return self.go_to(self.day + Duration::days(7)),
+            (Mode::Agenda, Action::Open) => {
+                return self.selected_object().map_or(Command::None, Command::Open);
+            }
+            (Mode::Month, Action::Up) => return self.go_to(self.day - Duration::days(7)),
+            (Mode::Month, Action::Down) => return self.go_to(self.day + Duration::days(7)),
+            (Mode::Month, Action::Left) => return self.go_to(self.day - Duration::days(1)),
+            (Mode::Month, Action::Right) => return self.go_to(self.day + Duration::days(1)),
+            // the agenda picks up from the day that was selected
+            (Mode::Month, Action::Open) => {
+                self.mode = Mode::Agenda;
+                return self.go_to(self.day);
+            }
+            (_, Action::PageUp) => return self.go_to(self.add_months(-1)),
+            (_, Action::PageDown) => return self.go_to(self.add_months(1)),
+            (_, Action::Today) => return self.go_to(Local::now().date_naive()),
+            (_, Action::SwitchView) => {
+                self.mode = if self.mode == Mode::Agenda { Mode::Month } else { Mode::Agenda };
+                return self.go_to(self.day);
+            }
+            (_, Action::Group) => return self.next_date(),
+            (_, Action::Back) => return Command::Goto(self.return_section),
+            _ => return Command::Unhandled,
+        }
+        Command::None
+    }
+
+    fn render(&self, layout: &Layout, _workspace: &Workspace) {
+        calendar_view(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_search,
+            self,
+        );
+    }
+}
+
+pub fn calendar_view(
+    content_width: &u16,
+    content_height: &u16,
+    x_center: &u16,
+    y_search: &u16,
+    calendar: &Calendar,
+) {
+    for i in 2..content_height - 4 {
+        execute!(
+            stdout(),
+            MoveTo(x_center - (content_width / 2) + 1, y_search + i + 1),
+            Print(" ".repeat((content_width - 2) as usize)),
+        )
+        .unwrap();
+    }
+
+    let width = content_width.saturating_sub(6) as usize;
+    let x_calendar = x_center - (content_width / 2) + 3;
+
+    let heading = match calendar.mode {
+        Mode::Agenda => format!("from {}", calendar.day.format("%-d %b %Y")),
+        Mode::Month => calendar.day.format("%B %Y").to_string(),
+    };
+    let title: String = format!("{} by {}, {}", calendar.database.title, calendar.date_column().name, heading)
+        .chars()
+        .take(width.saturating_sub(2))
+        .collect();
+    execute!(
+        stdout(),
+        MoveTo(x_calendar, y_search + 4),
+        SetBackgroundColor(Color::White),
+        SetForegroundColor(Color::Black),
+        Print(format!(" {} ", title)),
+        ResetColor
+    )
+    .unwrap();
+
+    if let Some(status) = &calendar.status {
+        let status: String = status.chars().take(width.saturating_sub(title.chars().count() + 4)).collect();
+        execute!(
+            stdout(),
+            MoveTo(x_calendar + width as u16 - status.chars().count() as u16, y_search + 4),
+            SetForegroundColor(Color::DarkGrey),
+            Print(status),
+            ResetColor
+        )
+        .unwrap();
+    }
+
+    let hint = match calendar.mode {
+        Mode::Agenda => "[enter] open  [←/→] week  [v] month view",
+        Mode::Month => "[enter] day  [pgup/pgdn] month  [v] agenda",
+    };
+    write_ctrl(
+        hint,
+        x_calendar + (width as u16).saturating_sub(hint.chars().count() as u16),
+        y_search + content_height - 5,
+    );
+
+    if let Some(message) = &calendar.message {
+        execute!(
+            stdout(),
+            MoveTo(x_calendar, y_search + 6),
+            SetForegroundColor(Color::DarkGrey),
+            Print(message),
+            ResetColor
+        )
+        .unwrap();
+        return;
+    }
+
+    match calendar.mode {
+        Mode::Agenda => agenda_view(content_height, &x_calendar, y_search, width, calendar),
+        Mode::Month => month_view(content_height, &x_calendar, y_search, width, calendar),
+    }
+}
+
+fn agenda_view(content_height: &u16, x_calendar: &u16, y_search: &u16, width: usize, calendar: &Calendar) {
+    let agenda = calendar.agenda();
+    if agenda.is_empty() {
+        execute!(
+            stdout(),
+            MoveTo(*x_calendar, y_search + 6),
+            SetForegroundColor(Color::DarkGrey),
+            Print(format!("nothing in the {} days from here", AGENDA_DAYS)),
+            ResetColor
+        )
+        .unwrap();
+        return;
+    }
+
+    // a heading line before each day's items, none for the item index
+    let mut lines: Vec<(NaiveDate, Option<usize>)> = Vec::new();
+    for (i, &(day, _)) in agenda.iter().enumerate() {
+        if lines.last().is_none_or(|&(last, _)| last != day) {
+            lines.push((day, None));
+        }
+        lines.push((day, Some(i)));
+    }
+
+    let today = Local::now().date_naive();
+    let count = (*content_height as usize).saturating_sub(12).max(1);
+    let selected = lines.iter().position(|&(_, item)| item == Some(calendar.pos)).unwrap_or(0);
+    let start = count * (selected / count);
+    let label_width = 13;
+
+    for (i, &(day, item)) in lines.iter().enumerate().skip(start).take(count) {
+        let y = y_search + 6 + (i - start) as u16;
+        match item {
+            None => {
+                let heading = if day == today {
+                    format!("{} · today", day.format("%a %-d %b"))
+                } else {
+                    day.format("%a %-d %b").to_string()
+                };
+                execute!(
+                    stdout(),
+                    MoveTo(*x_calendar, y),
+                    SetForegroundColor(if day == today { accent_color() } else { Color::White }),
+                    Print(heading.bold()),
+                    ResetColor
+                )
+                .unwrap();
+            }
+            Some(item) => {
+                let entry = &calendar.entries[agenda[item].1];
+                let chosen = item == calendar.pos;
+                execute!(
+                    stdout(),
+                    MoveTo(x_calendar + 2, y),
+                    SetForegroundColor(Color::DarkGrey),
+                    Print(fit(&entry.label_on(day), label_width)),
+                    SetForegroundColor(if chosen { Color::White } else { Color::Grey }),
+                    SetBackgroundColor(if chosen { accent_color() } else { Color::Reset }),
+                    Print(fit(&format!(" {}", entry.title), width.saturating_sub(label_width + 3))),
+                    ResetColor
+                )
+                .unwrap();
+            }
+        }
+    }
+}
+
+fn month_view(content_height: &u16, x_calendar: &u16, y_search: &u16, width: usize, calendar: &Calendar) {
+    let cell_width = width / 7;
+    let week_height = ((*content_height as usize).saturating_sub(13) / 6).max(2);
+    let today = Local::now().date_naive();
+
+    for (i, name) in ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"].iter().enumerate() {
+        execute!(
+            stdout(),
+            MoveTo(x_calendar + (i * cell_width) as u16, y_search + 6),
+            SetForegroundColor(Color::DarkGrey),
+            Print(name),
+            ResetColor
+        )
+        .unwrap();
+    }
+
+    let start = calendar.grid_start();
+    for offset in 0..42 {
+        let day = start + Duration::days(offset);
+        let x = x_calendar + ((offset as usize % 7) * cell_width) as u16;
+        let y = y_search + 7 + ((offset as usize / 7) * week_height) as u16;
+        let selected = day == calendar.day;
+        let in_month = day.month() == calendar.day.month();
+
+        execute!(
+            stdout(),
+            MoveTo(x, y),
+            SetForegroundColor(if selected {
+                Color::White
+            } else if day == today {
+                accent_color()
+            } else if in_month {
+                Color::Grey
+            } else {
+                Color::DarkGrey
+            }),
+            SetBackgroundColor(if selected { accent_color() } else { Color::Reset }),
+            Print(format!("{:>2}", day.day())),
+            ResetColor
+        )
+        .unwrap();
+
+        // as many titles as the cell has lines, the last one counting the rest
+        let entries = calendar.entries_on(day);
+        let room = week_height - 1;
+        for (line, &entry) in entries.iter().enumerate().take(room) {
+            let text = if line + 1 == room && entries.len() > room {
+                format!("+{} more", entries.len() - line)
+            } else {
+                calendar.entries[entry].title.clone()
+            };
+            execute!(
+                stdout(),
+                MoveTo(x, y + 1 + line as u16),
+                SetForegroundColor(if in_month { Color::Grey } else { Color::DarkGrey }),
+                Print(fit(&text, cell_width.saturating_sub(1))),
+                ResetColor
+            )
+            .unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    fn entry(start: &str, end: Option<&str>) -> Entry {
+        Entry::new("id".to_string(), "title".to_string(), &json!({ "start": start, "end": end })).unwrap()
+    }
+
+    #[test]
+    fn moments() {
+        assert_eq!(parse_moment("2024-05-01"), Some((day("2024-05-01"), None)));
+        assert_eq!(
+            parse_moment("2024-05-01T09:30:00.000"),
+            Some((day("2024-05-01"), NaiveTime::from_hms_opt(9, 30, 0)))
+        );
+        // offsets are moved into local time
+        let local = DateTime::parse_from_rfc3339("2024-05-01T09:30:00.000+02:00").unwrap().with_timezone(&Local);
+        assert_eq!(
+            parse_moment("2024-05-01T09:30:00.000+02:00"),
+            Some((local.date_naive(), Some(local.time())))
+        );
+        assert_eq!(parse_moment("May 1st"), None);
+    }
+
+    #[test]
+    fn all_day_ranges() {
+        assert_eq!(entry("2024-05-01", None).label_on(day("2024-05-01")), "all day");
+
+        let range = entry("2024-05-01", Some("2024-05-03"));
+        assert_eq!(range.end, day("2024-05-03"));
+        assert_eq!(range.label_on(day("2024-05-01")), "day 1/3");
+        assert_eq!(range.label_on(day("2024-05-03")), "day 3/3");
+    }
+
+    #[test]
+    fn timed_entries() {
+        let meeting = entry("2024-05-01T09:30:00.000", Some("2024-05-01T10:00:00.000"));
+        assert_eq!(meeting.label_on(day("2024-05-01")), "09:30–10:00");
+        assert_eq!(entry("2024-05-01T09:30:00.000", None).label_on(day("2024-05-01")), "09:30");
+
+        let trip = entry("2024-05-01T18:00:00.000", Some("2024-05-03T08:15:00.000"));
+        assert_eq!(trip.label_on(day("2024-05-01")), "from 18:00");
+        assert_eq!(trip.label_on(day("2024-05-02")), "all day");
+        assert_eq!(trip.label_on(day("2024-05-03")), "until 08:15");
+    }
+
+    #[test]
+    fn ends_before_the_start_are_dropped() {
+        let backwards = entry("2024-05-03", Some("2024-05-01"));
+        assert_eq!((backwards.start, backwards.end), (day("2024-05-03"), day("2024-05-03")));
+        assert_eq!(backwards.label_on(day("2024-05-03")), "all day");
+        assert!(Entry::new("id".to_string(), "title".to_string(), &json!(null)).is_none());
+    }
+}
@@ -6,12 +6,75 @@ use crossterm::{
     style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
 };
 
+use crossterm::event::KeyEvent;
+
+use crate::{
+    app::Workspace,
+    database::favorites::{move_favorite, toggle_favorite, Favorite},
+    utils::{keymap::Action, theme::favorite_color},
+    views::{wrap_pos, Command, Layout, View},
+};
+
+#[derive(Default)]
+pub struct FavoritesView {
+    pub pos: usize,
+}
+
+impl View for FavoritesView {
+    fn update(&mut self, action: Action, _key: &KeyEvent, workspace: &mut Workspace) -> Command {
+        match action {
+            Action::Up | Action::Down => {
+                let delta = if action == Action::Up { -1 } else { 1 };
+                self.pos = wrap_pos(self.pos, delta, workspace.favorites.len());
+            }
+            Action::MoveUp | Action::MoveDown => {
+                let delta = if action == Action::MoveUp { -1 } else { 1 };
+                move_favorite(&workspace.favorites, self.pos, delta);
+                workspace.reload_favorites();
+                self.pos = self
+                    .pos
+                    .saturating_add_signed(delta)
+                    .min(workspace.favorites.len().saturating_sub(1));
+            }
+            Action::Open => {
+                if let Some(favorite) = workspace.favorites.get(self.pos) {
+                    return Command::Open(favorite.to_object());
+                }
+            }
+            Action::Favorite => {
+                if let Some(favorite) = workspace.favorites.get(self.pos) {
+                    toggle_favorite(&favorite.to_object());
+                    workspace.reload_favorites();
+                    self.pos = self.pos.min(workspace.favorites.len().saturating_sub(1));
+                }
+            }
+            _ => return Command::Unhandled,
+        }
+        Command::None
+    }
+
+    fn enter(&mut self, _workspace: &Workspace) {
+        self.pos = 0;
+    }
+
+    fn render(&self, layout: &Layout, workspace: &Workspace) {
+        favorites_view(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_search,
+            &workspace.favorites,
+            self.pos,
+        );
+    }
+}
+
 pub fn favorites_view(
     content_width: &u16,
     content_height: &u16,
     x_center: &u16,
     y_search: &u16,
-    favorites: &Vec<&str>,
+    favorites: &[Favorite],
     favorites_pos: usize,
 ) {
     for i in 2..content_height - 4 {
@@ -53,15 +116,22 @@ pub fn favorites_view(
                 Color::DarkGrey
             }),
             SetBackgroundColor(if i == (favorites_pos % count) {
-                Color::Rgb {
-                    r: 252,
-                    g: 186,
-                    b: 3,
-                }
+                favorite_color()
             } else {
                 Color::Reset
             }),
-            Print(favorite),
+            Print(&favorite.title),
+            ResetColor
+        )
+        .unwrap();
+    }
+
+    if favorites.is_empty() {
+        execute!(
+            stdout(),
+            MoveTo(x_center - (content_width / 2) + 10, y_search + 9),
+            SetForegroundColor(Color::DarkGrey),
+            Print("press [*] in pages or tables to add one"),
             ResetColor
         )
         .unwrap();
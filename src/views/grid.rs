@@ -0,0 +1,476 @@
+use std::{error::Error, io::stdout};
+
+use crossterm::{
+    cursor::MoveTo,
+    execute,
+    style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor, Stylize},
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use serde_json::Value;
+
+use crate::{
+    api::{
+        databases::{get_columns, property_text, query_database, Column, Row},
+        search::{find_by_id, Object},
+    },
+    app::{Section, Workspace},
+    database::queries::{get_queries, save_query, SavedQuery},
+    utils::{dimentions::get_dimensions, keymap::Action, theme::accent_color},
+    views::{confirmation, Command, Layout, View},
+};
+
+const MAX_COLUMN_WIDTH: usize = 24;
+
+// text typed under the title, either a query to run or a name to save it as
+pub enum Prompt {
+    Query(String),
+    Name(String),
+}
+
+pub struct Grid {
+    pub database: Object,
+    pub columns: Vec<Column>,
+    pub rows: Vec<Row>,
+    pub cells: Vec<Vec<String>>,
+    pub row_pos: usize,
+    pub column_pos: usize,
+    pub message: Option<String>,
+    // outcome of the last archive, shown beside the title
+    pub status: Option<String>,
+    // the selected row is waiting on confirmation before it is archived
+    pub archiving: bool,
+    // the query the rows were filtered with, empty for every row
+    pub query: String,
+    pub prompt: Option<Prompt>,
+    // queries saved for this database, cycled with tab in the query prompt
+    saved: Vec<SavedQuery>,
+    saved_pos: usize,
+    pub return_section: Section,
+}
+
+impl Grid {
+    pub fn new(database: Object, return_section: Section) -> Self {
+        Self {
+            database,
+            columns: Vec::new(),
+            rows: Vec::new(),
+            cells: Vec::new(),
+            row_pos: 0,
+            column_pos: 1,
+            message: Some("loading...".to_string()),
+            status: None,
+            archiving: false,
+            query: String::new(),
+            prompt: None,
+            saved: Vec::new(),
+            saved_pos: 0,
+            return_section,
+        }
+    }
+
+    pub fn open_query(&mut self) {
+        self.saved = get_queries(Some(&self.database.id)).unwrap_or_default();
+        self.saved_pos = 0;
+        self.prompt = Some(Prompt::Query(self.query.clone()));
+    }
+
+    fn prompt_update(&mut self, action: Action, key: &KeyEvent) -> Command {
+        let Some(prompt) = self.prompt.as_mut() else {
+            return Command::Unhandled;
+        };
+        let naming = matches!(prompt, Prompt::Name(_));
+        let text = match prompt {
+            Prompt::Query(text) | Prompt::Name(text) => text,
+        };
+        match (action, key.code) {
+            (Action::Type, KeyCode::Char(c)) => text.push(c),
+            (Action::Erase, _) => {
+                text.pop();
+            }
+            (Action::Cycle, _) if !naming && !self.saved.is_empty() => {
+                *text = self.saved[self.saved_pos].query.clone();
+                self.saved_pos = (self.saved_pos + 1) % self.saved.len();
+            }
+            (Action::Back, _) => self.prompt = None,
+            (Action::Open, _) => {
+                let text = text.trim().to_string();
+                let prompt = self.prompt.take();
+                match prompt {
+                    Some(Prompt::Query(_)) => return Command::Query(text),
+                    Some(Prompt::Name(_)) if !text.is_empty() => {
+                        let name = text.trim_start_matches('@').to_string();
+                        let saved = save_query(&SavedQuery {
+                            name: name.clone(),
+                            database_id: self.database.id.clone(),
+                            database_title: self.database.title.clone(),
+                            query: self.query.clone(),
+                        });
+                        self.status = Some(match saved {
+                            true => format!("saved as @{}", name),
+                            false => format!("@{} belongs to another database, pick another name", name),
+                        });
+                    }
+                    _ => {}
+                }
+            }
+            _ => return Command::Unhandled,
+        }
+        Command::None
+    }
+
+    // the grid shows a failure itself, the error is for callers that wanted the rows
+    pub async fn fetch(&mut self, tree: &[Object]) -> Result<(), Box<dyn Error>> {
+        match (get_columns(&self.database.id).await, query_database(&self.database.id).await) {
+            (Ok(columns), Ok(rows)) => {
+                self.load(columns, rows, tree);
+                Ok(())
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                self.message = Some(format!("failed to load database: {}", e));
+                Err(e)
+            }
+        }
+    }
+
+    // store the rows and flatten every property into its cell text
+    pub fn load(&mut self, columns: Vec<Column>, rows: Vec<Row>, tree: &[Object]) {
+        self.cells = rows.iter().map(|row| row_cells(&columns, row, tree)).collect();
+        self.columns = columns;
+        self.rows = rows;
+        self.message = if self.rows.is_empty() {
+            Some("no rows".to_string())
+        } else {
+            None
+        };
+    }
+
+    // take the properties notion returned after an edit, giving back the row's new cells
+    pub fn update_row(&mut self, id: &str, properties: Value, tree: &[Object]) -> Option<Vec<String>> {
+        let index = self.rows.iter().position(|row| row.id == id)?;
+        self.rows[index].properties = properties;
+        self.cells[index] = row_cells(&self.columns, &self.rows[index], tree);
+        Some(self.cells[index].clone())
+    }
+
+    pub fn move_row(&mut self, delta: isize) {
+        if self.rows.is_empty() {
+            return;
+        }
+        self.row_pos = self
+            .row_pos
+            .saturating_add_signed(delta)
+            .min(self.rows.len() - 1);
+    }
+
+    pub fn move_column(&mut self, delta: isize) {
+        let last = self.columns.len().saturating_sub(1).max(1);
+        self.column_pos = self.column_pos.saturating_add_signed(delta).clamp(1, last);
+    }
+
+    pub fn selected_row(&self) -> Option<&Row> {
+        self.rows.get(self.row_pos)
+    }
+
+    // rows are pages, so they open in the reader and archive like pages
+    pub fn selected_object(&self) -> Option<Object> {
+        self.selected_row().map(|row| Object {
+            id: row.id.clone(),
+            parent_id: Some(self.database.id.clone()),
+            title: self.cells[self.row_pos][0].clone(),
+            object_type: "page".to_string(),
+            children: Vec::new(),
+            last_edited_time: None,
+        })
+    }
+
+    // drop a row that was archived
+    pub fn remove_row(&mut self, id: &str) {
+        if let Some(index) = self.rows.iter().position(|row| row.id == id) {
+            self.rows.remove(index);
+            self.cells.remove(index);
+            self.row_pos = self.row_pos.min(self.rows.len().saturating_sub(1));
+            if self.rows.is_empty() {
+                self.message = Some("no rows".to_string());
+            }
+        }
+    }
+
+    fn column_widths(&self) -> Vec<usize> {
+        self.columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                self.cells
+                    .iter()
+                    .map(|row| row[i].chars().count())
+                    .chain([column.name.chars().count()])
+                    .max()
+                    .unwrap_or(0)
+                    .min(MAX_COLUMN_WIDTH)
+            })
+            .collect()
+    }
+
+    pub fn page_height(&self) -> isize {
+        let (_, content_height, _, _) = get_dimensions();
+        grid_height(content_height) as isize
+    }
+}
+
+impl View for Grid {
+    fn update(&mut self, action: Action, key: &KeyEvent, _workspace: &mut Workspace) -> Command {
+        if self.prompt.is_some() {
+            return self.prompt_update(action, key);
+        }
+        if self.archiving {
+            let Some(confirmed) = confirmation(action, key) else {
+                return Command::Unhandled;
+            };
+            self.archiving = false;
+            self.status = None;
+            return match self.selected_object() {
+                Some(object) if confirmed => Command::Archive(object),
+                _ => Command::None,
+            };
+        }
+
+        self.status = None;
+        match action {
+            Action::Up => self.move_row(-1),
+            Action::Down => self.move_row(1),
+            Action::PageUp => self.move_row(-self.page_height()),
+            Action::PageDown => self.move_row(self.page_height()),
+            Action::Left => self.move_column(-1),
+            Action::Right => self.move_column(1),
+            Action::Open => return self.selected_object().map_or(Command::None, Command::Open),
+            Action::Edit if self.selected_row().is_some() => return Command::EditRow,
+            Action::Board => return Command::Board(self.database.clone()),
+            Action::Calendar => return Command::Calendar(self.database.clone()),
+            Action::Archive => {
+                if let Some(object) = self.selected_object() {
+                    self.archiving = true;
+                    self.status = Some(format!("archive {}? [y/n]", object.title));
+                }
+            }
+            Action::Query => self.open_query(),
+            Action::Save if self.query.is_empty() => self.status = Some("no query to save".to_string()),
+            Action::Save => self.prompt = Some(Prompt::Name(String::new())),
+            Action::Back => return Command::Goto(self.return_section),
+            _ => return Command::Unhandled,
+        }
+        Command::None
+    }
+
+    fn editing(&self) -> bool {
+        self.archiving || self.prompt.is_some()
+    }
+
+    fn render(&self, layout: &Layout, _workspace: &Workspace) {
+        grid_view(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_search,
+            self,
+        );
+    }
+}
+
+fn grid_height(content_height: u16) -> usize {
+    content_height.saturating_sub(11) as usize
+}
+
+pub fn row_cells(columns: &[Column], row: &Row, tree: &[Object]) -> Vec<String> {
+    columns
+        .iter()
+        .map(|column| {
+            let property = &row.properties[&column.name];
+            if column.property_type == "relation" {
+                relation_text(property, tree)
+            } else {
+                property_text(property)
+            }
+        })
+        .collect()
+}
+
+// relations resolved to titles where the page is known in the workspace
+fn relation_text(property: &Value, tree: &[Object]) -> String {
+    property["relation"]
+        .as_array()
+        .map(|pages| {
+            pages
+                .iter()
+                .filter_map(|page| page["id"].as_str())
+                .map(|id| find_by_id(tree, id).map_or(id.to_string(), |page| page.title.clone()))
+                .collect::<Vec<String>>()
+                .join(", ")
+        })
+        .unwrap_or_default()
+}
+
+// cut text to width, marking truncation with an ellipsis
+pub fn fit(text: &str, width: usize) -> String {
+    let length = text.chars().count();
+    if length > width {
+        let cut: String = text.chars().take(width.saturating_sub(1)).collect();
+        format!("{}…", cut)
+    } else {
+        format!("{}{}", text, " ".repeat(width - length))
+    }
+}
+
+pub fn grid_view(
+    content_width: &u16,
+    content_height: &u16,
+    x_center: &u16,
+    y_search: &u16,
+    grid: &Grid,
+) {
+    for i in 2..content_height - 4 {
+        execute!(
+            stdout(),
+            MoveTo(x_center - (content_width / 2) + 1, y_search + i + 1),
+            Print(" ".repeat((content_width - 2) as usize)),
+        )
+        .unwrap();
+    }
+
+    let width = content_width.saturating_sub(6) as usize;
+    let x_grid = x_center - (content_width / 2) + 3;
+
+    let title: String = grid.database.title.chars().take(width.saturating_sub(2)).collect();
+    execute!(
+        stdout(),
+        MoveTo(x_grid, y_search + 4),
+        SetBackgroundColor(Color::White),
+        SetForegroundColor(Color::Black),
+        Print(format!(" {} ", title)),
+        ResetColor
+    )
+    .unwrap();
+
+    if let Some(status) = &grid.status {
+        let status: String = status.chars().take(width.saturating_sub(title.chars().count() + 4)).collect();
+        execute!(
+            stdout(),
+            MoveTo(x_grid + width as u16 - status.chars().count() as u16, y_search + 4),
+            SetForegroundColor(if grid.archiving { Color::White } else { Color::DarkGrey }),
+            Print(status),
+            ResetColor
+        )
+        .unwrap();
+    }
+
+    // the query line, typed into or showing what the rows are filtered by
+    let query = match &grid.prompt {
+        Some(Prompt::Query(text)) => Some((format!("/ {}_", text), Color::White)),
+        Some(Prompt::Name(text)) => Some((format!("save as @{}_", text), Color::White)),
+        None if !grid.query.is_empty() => Some((format!("/ {}", grid.query), Color::DarkGrey)),
+        None => None,
+    };
+    if let Some((line, color)) = query {
+        let line: String = line.chars().rev().take(width).collect::<Vec<char>>().into_iter().rev().collect();
+        execute!(
+            stdout(),
+            MoveTo(x_grid, y_search + 5),
+            SetForegroundColor(color),
+            Print(line),
+            ResetColor
+        )
+        .unwrap();
+    }
+
+    if let Some(message) = &grid.message {
+        execute!(
+            stdout(),
+            MoveTo(x_grid, y_search + 6),
+            SetForegroundColor(Color::DarkGrey),
+            Print(message),
+            ResetColor
+        )
+        .unwrap();
+        return;
+    }
+
+    // title column stays pinned, the rest scroll from `column_pos`
+    let widths = grid.column_widths();
+    if widths.is_empty() {
+        return;
+    }
+    let mut visible = vec![(0, widths[0].min(width))];
+    let mut used = visible[0].1;
+    for (i, &column_width) in widths.iter().enumerate().skip(grid.column_pos) {
+        let remaining = width.saturating_sub(used + 3);
+        if remaining < 4 {
+            break;
+        }
+        visible.push((i, column_width.min(remaining)));
+        used += 3 + column_width.min(remaining);
+        if column_width > remaining {
+            break;
+        }
+    }
+
+    let header: Vec<String> = visible
+        .iter()
+        .map(|&(i, column_width)| fit(&grid.columns[i].name, column_width))
+        .collect();
+    execute!(
+        stdout(),
+        MoveTo(x_grid, y_search + 6),
+        SetForegroundColor(Color::White),
+        Print(header.join(" │ ").bold()),
+        MoveTo(x_grid, y_search + 7),
+        SetForegroundColor(Color::DarkGrey),
+        Print("─".repeat(width)),
+        ResetColor
+    )
+    .unwrap();
+
+    let count = grid_height(*content_height).max(1);
+    let start = count * (grid.row_pos / count);
+    for (i, row) in grid.cells.iter().enumerate().skip(start).take(count) {
+        let line: Vec<String> = visible
+            .iter()
+            .map(|&(column, column_width)| fit(&row[column], column_width))
+            .collect();
+        let selected = i == grid.row_pos;
+        execute!(
+            stdout(),
+            MoveTo(x_grid, y_search + 8 + (i - start) as u16),
+            SetForegroundColor(if selected { Color::White } else { Color::DarkGrey }),
+            SetBackgroundColor(if selected {
+                accent_color()
+            } else {
+                Color::Reset
+            }),
+            Print(fit(&line.join(" │ "), width)),
+            ResetColor
+        )
+        .unwrap();
+    }
+
+    // column scroll markers
+    let y_marker = y_search + 6;
+    if grid.column_pos > 1 {
+        execute!(
+            stdout(),
+            MoveTo(x_grid - 2, y_marker),
+            SetForegroundColor(Color::DarkGrey),
+            Print("←"),
+            ResetColor
+        )
+        .unwrap();
+    }
+    if visible.last().is_some_and(|&(last, _)| last + 1 < grid.columns.len()) {
+        execute!(
+            stdout(),
+            MoveTo(x_center + (content_width / 2) - 2, y_marker),
+            SetForegroundColor(Color::DarkGrey),
+            Print("→"),
+            ResetColor
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,114 @@
+use std::io::stdout;
+
+use crossterm::{
+    cursor::MoveTo,
+    execute,
+    style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
+};
+
+use crate::{
+    app::Section,
+    utils::keymap::{bindings_for, Binding, Mode, Scope},
+};
+
+// one titled group of bindings in a help column
+fn write_group(title: &str, bindings: &[&Binding], x: u16, y: u16, bottom: u16, width: usize) -> u16 {
+    if bindings.is_empty() || y >= bottom {
+        return y;
+    }
+
+    execute!(
+        stdout(),
+        MoveTo(x, y),
+        SetForegroundColor(Color::White),
+        Print(title),
+        ResetColor
+    )
+    .unwrap();
+
+    let label_width = bindings
+        .iter()
+        .map(|binding| binding.key.label().chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut y = y + 1;
+    for binding in bindings {
+        if y >= bottom {
+            break;
+        }
+        let label = binding.key.label();
+        let description: String = binding
+            .description
+            .chars()
+            .take(width.saturating_sub(label_width + 3))
+            .collect();
+        execute!(
+            stdout(),
+            MoveTo(x, y),
+            SetForegroundColor(Color::White),
+            Print(format!(
+                "{}{}",
+                label,
+                " ".repeat(label_width - label.chars().count() + 2)
+            )),
+            SetForegroundColor(Color::DarkGrey),
+            Print(description),
+            ResetColor
+        )
+        .unwrap();
+        y += 1;
+    }
+
+    y + 1
+}
+
+pub fn help_view(
+    content_width: &u16,
+    content_height: &u16,
+    x_center: &u16,
+    y_search: &u16,
+    section: Section,
+) {
+    for i in 2..content_height - 4 {
+        execute!(
+            stdout(),
+            MoveTo(x_center - (content_width / 2) + 1, y_search + i + 1),
+            Print(" ".repeat((content_width - 2) as usize)),
+        )
+        .unwrap();
+    }
+
+    execute!(
+        stdout(),
+        MoveTo(x_center - (content_width / 2) + 3, y_search + 4),
+        SetBackgroundColor(Color::White),
+        SetForegroundColor(Color::Black),
+        Print(" help ".to_string()),
+        ResetColor
+    )
+    .unwrap();
+
+    // the current section and search on the left, global keys on the right
+    let (local, global): (Vec<&Binding>, Vec<&Binding>) = bindings_for(Mode::Normal, section)
+        .into_iter()
+        .partition(|binding| !matches!(binding.scope, Scope::Global));
+    let search = bindings_for(Mode::Search, section);
+
+    let column_width = (content_width.saturating_sub(8) / 2) as usize;
+    let x_left = x_center - (content_width / 2) + 3;
+    let x_right = x_left + column_width as u16 + 2;
+    let bottom = y_search + content_height - 5;
+
+    let y = write_group(section.name(), &local, x_left, y_search + 6, bottom, column_width);
+    write_group("search", &search, x_left, y, bottom, column_width);
+    write_group("everywhere", &global, x_right, y_search + 6, bottom, column_width);
+
+    execute!(
+        stdout(),
+        MoveTo(x_left, bottom),
+        SetForegroundColor(Color::DarkGrey),
+        Print("press any key to close"),
+        ResetColor
+    )
+    .unwrap();
+}
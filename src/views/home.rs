@@ -2,7 +2,26 @@ use std::io::stdout;
 
 use crossterm::{cursor::MoveTo, execute, style::Print};
 
-use crate::{components::controls::controls, utils::controls::write_ctrl, AUTHENTICATED};
+use crate::{
+    app::Workspace,
+    components::controls::controls,
+    utils::controls::write_ctrl,
+    views::{Layout, View},
+};
+
+pub struct HomeView;
+
+impl View for HomeView {
+    fn render(&self, layout: &Layout, _workspace: &Workspace) {
+        home_view(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_center,
+            &layout.y_search,
+        );
+    }
+}
 
 pub fn home_view(
     content_width: &u16,
@@ -22,7 +41,7 @@ pub fn home_view(
 
     controls(false, false);
 
-    let options = vec!["[f]avorites", "[p]ages", "[t]ables", "[r]ecents"];
+    let options = ["[f]avorites", "[p]ages", "[t]ables", "[r]ecents", "[a]rchived", "[x] todos", "[c]onfig"];
 
     for (i, option) in options.iter().enumerate() {
         let x_option = x_center - (options[1].len() as u16 / 2) - 3;
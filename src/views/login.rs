@@ -2,7 +2,25 @@ use std::io::stdout;
 
 use crossterm::{cursor::MoveTo, execute, style::{Print, SetForegroundColor, Color}};
 
-use crate::components::controls::controls;
+use crate::{
+    app::Workspace,
+    components::controls::controls,
+    views::{Layout, View},
+};
+
+pub struct LoginView;
+
+impl View for LoginView {
+    fn render(&self, layout: &Layout, _workspace: &Workspace) {
+        login_view(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_center,
+            &layout.y_search,
+        );
+    }
+}
 
 pub fn login_view(
     content_width: &u16,
@@ -26,7 +44,7 @@ pub fn login_view(
     
     execute!(
         stdout(),
-        MoveTo(x_center - (text.len() as u16 / 2), y_center-0),
+        MoveTo(x_center - (text.len() as u16 / 2), *y_center),
         SetForegroundColor(Color::White),
         Print(text),
         MoveTo(x_center - (subtext.len() as u16 / 2), y_center+2),
@@ -1,6 +1,116 @@
 pub mod login;
 pub mod auth;
+pub mod board;
+pub mod calendar;
 pub mod home;
 pub mod pages;
 pub mod tables;
 pub mod favorites;
+pub mod grid;
+pub mod reader;
+pub mod row;
+pub mod recent;
+pub mod settings;
+pub mod trash;
+pub mod todos;
+pub mod help;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use serde_json::Value;
+
+use crate::{
+    api::{search::Object, todos::Todo},
+    app::{Section, Workspace},
+    utils::{dimentions::get_dimensions, keymap::Action},
+};
+
+// where the content area sits on screen, shared by every view's render
+pub struct Layout {
+    pub content_width: u16,
+    pub content_height: u16,
+    pub x_center: u16,
+    pub y_center: u16,
+    pub y_search: u16,
+}
+
+impl Layout {
+    pub fn current() -> Self {
+        let (content_width, content_height, x_center, y_center) = get_dimensions();
+        Self {
+            content_width,
+            content_height,
+            x_center,
+            y_center,
+            y_search: y_center - (content_height / 2) + 1,
+        }
+    }
+}
+
+// step a list position by delta, wrapping around either end
+pub fn wrap_pos(pos: usize, delta: isize, len: usize) -> usize {
+    if len == 0 {
+        return 0;
+    }
+    (pos as isize + delta).rem_euclid(len as isize) as usize
+}
+
+// the answer to a yes/no prompt, [y] or [enter] for yes and anything typed or [esc] for no
+pub fn confirmation(action: Action, key: &KeyEvent) -> Option<bool> {
+    match (action, key.code) {
+        (Action::Open, _) | (Action::Type, KeyCode::Char('y' | 'Y')) => Some(true),
+        (Action::Type | Action::Back, _) => Some(false),
+        _ => None,
+    }
+}
+
+// what a view asks the app to do after handling an action
+pub enum Command {
+    // handled, redraw the content area
+    None,
+    // handled, redraw the whole screen
+    Redraw,
+    // open a page in the reader or a database in the grid
+    Open(Object),
+    // create a titled page under a page, or a row in a database, from a named template
+    Create(Object, String, Option<String>),
+    // hand the open page to $EDITOR and sync the result back
+    Edit,
+    // trash a confirmed page, database or row, or bring one back
+    Archive(Object),
+    Restore(Object),
+    // check off a to-do block or row through the api
+    Check(Todo),
+    // pick a new parent for a page with the search box
+    Move(Object),
+    // show a database's rows as cards grouped by a select or status property
+    Board(Object),
+    // show a database's rows by date, as an agenda or a month
+    Calendar(Object),
+    // the view's data has to be fetched again
+    Refresh,
+    // open the selected grid row's properties as a form
+    EditRow,
+    // patch one property of a row, which the view already shows as changed
+    SetProperty(String, String, Value),
+    // filter and sort the grid's rows with a query, every row when it is empty
+    Query(String),
+    Goto(Section),
+    // the view has no use for the action
+    Unhandled,
+}
+
+pub trait View {
+    fn update(&mut self, _action: Action, _key: &KeyEvent, _workspace: &mut Workspace) -> Command {
+        Command::Unhandled
+    }
+
+    // true while the view captures typed text
+    fn editing(&self) -> bool {
+        false
+    }
+
+    // called when the section is picked from the menu rather than returned to
+    fn enter(&mut self, _workspace: &Workspace) {}
+
+    fn render(&self, layout: &Layout, workspace: &Workspace);
+}
@@ -6,16 +6,239 @@ use crossterm::{
     style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
 };
 
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    api::search::Object,
+    app::Workspace,
+    database::{
+        favorites::{toggle_favorite, Favorite},
+        templates::get_templates,
+        todo_sources::toggle_todo_source,
+    },
+    utils::{
+        controls::write_ctrl,
+        keymap::Action,
+        theme::{accent_color, favorite_color, secondary_color},
+    },
+    views::{confirmation, grid::fit, wrap_pos, Command, Layout, View},
+};
+
+// top level pages on the left, the highlighted page's children on the right
+#[derive(Default)]
+pub struct PagesView {
+    pub pos: usize,
+    pub selected: bool,
+    pub child_pos: usize,
+    // title typed so far for a new page under the highlighted item
+    pub creating: Option<String>,
+    // saved template names, the chosen one is `template - 1` and 0 means none
+    pub templates: Vec<String>,
+    pub template: usize,
+    // item waiting on confirmation before it is archived
+    pub archiving: Option<Object>,
+    pub message: Option<String>,
+}
+
+impl PagesView {
+    fn chosen_template(&self) -> Option<&String> {
+        self.template.checked_sub(1).and_then(|index| self.templates.get(index))
+    }
+
+    // keep the positions inside the tree after items were moved or removed
+    pub fn clamp(&mut self, tree: &[Object]) {
+        self.pos = self.pos.min(tree.len().saturating_sub(1));
+        let children = tree.get(self.pos).map_or(0, |page| page.children.len());
+        self.child_pos = self.child_pos.min(children.saturating_sub(1));
+        self.selected &= children > 0;
+    }
+
+    // the question along the bottom and the keys that answer it
+    fn prompt(&self, tree: &[Object]) -> Option<(String, &'static str)> {
+        if let Some(object) = &self.archiving {
+            return Some((
+                format!(" archive {} {}? ", object.object_type, object.title),
+                "[y] archive  [esc] cancel",
+            ));
+        }
+
+        let (title, parent) = (self.creating.as_ref()?, self.highlighted(tree)?);
+        let kind = if parent.object_type == "database" { "row in" } else { "page under" };
+        let from = self.chosen_template().map(|name| format!(" from {}", name)).unwrap_or_default();
+        let hint = if self.templates.is_empty() {
+            "[enter] create  [esc] cancel"
+        } else {
+            "[tab] template  [enter] create  [esc] cancel"
+        };
+        Some((format!(" new {} {}{}: {}_ ", kind, parent.title, from, title), hint))
+    }
+
+    fn highlighted<'a>(&self, tree: &'a [Object]) -> Option<&'a Object> {
+        let page = tree.get(self.pos)?;
+        if self.selected {
+            page.children.get(self.child_pos)
+        } else {
+            Some(page)
+        }
+    }
+}
+
+impl View for PagesView {
+    fn update(&mut self, action: Action, key: &KeyEvent, workspace: &mut Workspace) -> Command {
+        let pages = &workspace.tree;
+        if self.archiving.is_some() {
+            return match confirmation(action, key) {
+                Some(true) => self.archiving.take().map_or(Command::None, Command::Archive),
+                Some(false) => {
+                    self.archiving = None;
+                    Command::None
+                }
+                None => Command::Unhandled,
+            };
+        }
+        if let Some(title) = self.creating.as_mut() {
+            match action {
+                Action::Type => {
+                    if let KeyCode::Char(c) = key.code {
+                        title.push(c);
+                    }
+                }
+                Action::Erase => {
+                    title.pop();
+                }
+                Action::Cycle => self.template = (self.template + 1) % (self.templates.len() + 1),
+                Action::Open => {
+                    let title = self.creating.take().unwrap_or_default();
+                    if let (Some(parent), false) = (self.highlighted(pages), title.trim().is_empty()) {
+                        let template = self.chosen_template().cloned();
+                        return Command::Create(parent.clone(), title.trim().to_string(), template);
+                    }
+                }
+                Action::Back => self.creating = None,
+                _ => return Command::Unhandled,
+            }
+            return Command::None;
+        }
+
+        self.message = None;
+        match action {
+            Action::Up | Action::Down => {
+                let delta = if action == Action::Up { -1 } else { 1 };
+                if self.selected {
+                    let children = pages.get(self.pos).map_or(0, |page| page.children.len());
+                    self.child_pos = wrap_pos(self.child_pos, delta, children);
+                } else {
+                    self.pos = wrap_pos(self.pos, delta, pages.len());
+                }
+                Command::None
+            }
+            // pages without children open directly, the rest expand into the right column
+            Action::Open => match pages.get(self.pos) {
+                Some(_) if self.selected => self
+                    .highlighted(pages)
+                    .map_or(Command::None, |object| Command::Open(object.clone())),
+                Some(page) if page.children.is_empty() => Command::Open(page.clone()),
+                Some(_) => {
+                    self.selected = true;
+                    self.child_pos = 0;
+                    Command::None
+                }
+                None => Command::None,
+            },
+            Action::OpenPage => match pages.get(self.pos) {
+                Some(page) if page.object_type == "page" => Command::Open(page.clone()),
+                _ => Command::None,
+            },
+            Action::NewPage => {
+                if self.highlighted(pages).is_some() {
+                    self.creating = Some(String::new());
+                    self.templates = get_templates()
+                        .map(|templates| templates.into_iter().map(|template| template.name).collect())
+                        .unwrap_or_default();
+                    self.template = 0;
+                }
+                Command::None
+            }
+            Action::Archive => {
+                self.archiving = self.highlighted(pages).cloned();
+                Command::None
+            }
+            Action::Move => match self.highlighted(pages) {
+                Some(object) if object.object_type == "page" => Command::Move(object.clone()),
+                Some(object) => {
+                    self.message = Some(format!("\"{}\" is a database, only pages can be moved", object.title));
+                    Command::None
+                }
+                None => Command::None,
+            },
+            Action::TodoSource => {
+                if let Some(object) = self.highlighted(pages) {
+                    self.message = Some(if toggle_todo_source(object) {
+                        format!("collecting todos from {}, [x] to see them", object.title)
+                    } else {
+                        format!("no longer collecting todos from {}", object.title)
+                    });
+                }
+                Command::None
+            }
+            Action::Favorite => {
+                if let Some(object) = self.highlighted(pages) {
+                    toggle_favorite(object);
+                }
+                workspace.reload_favorites();
+                Command::None
+            }
+            Action::Back if self.selected => {
+                self.selected = false;
+                self.child_pos = 0;
+                Command::None
+            }
+            _ => Command::Unhandled,
+        }
+    }
+
+    fn editing(&self) -> bool {
+        self.creating.is_some() || self.archiving.is_some()
+    }
+
+    fn enter(&mut self, _workspace: &Workspace) {
+        *self = Self::default();
+    }
+
+    fn render(&self, layout: &Layout, workspace: &Workspace) {
+        pages_view(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_search,
+            &workspace.tree,
+            self.pos,
+            self.selected,
+            self.child_pos,
+            &workspace.favorites,
+        );
+        pages_prompt(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_search,
+            self.prompt(&workspace.tree),
+            self.message.as_deref(),
+        );
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn pages_view(
     content_width: &u16,
     content_height: &u16,
     x_center: &u16,
     y_search: &u16,
-    pages: &indexmap::IndexMap<&str, Vec<&str>>,
+    pages: &[Object],
     page_pos: usize,
     page_selected: bool,
     table_pos: usize,
-    favorites: &Vec<&str>,
+    favorites: &[Favorite],
 ) {
     for i in 2..content_height - 4 {
         execute!(
@@ -36,15 +259,26 @@ pub fn pages_view(
     )
     .unwrap();
 
-    let selected_page = pages.keys().nth(page_pos).unwrap();
-    let contents = pages.get(selected_page).unwrap();
+    let contents = match pages.get(page_pos) {
+        Some(page) => &page.children,
+        None => return,
+    };
+
+    // both columns page through as many rows as fit above the prompt line
+    let count = (*content_height as usize).saturating_sub(15).max(1);
+    // the left column runs up to the right one at +35, the right one to the frame
+    let page_width = 23;
+    let contents_width = (*content_width as usize).saturating_sub(47).max(1);
+    let page_start = count * (page_pos / count);
+    let content_start = count * (table_pos / count);
 
-    for (i, (page, _contents)) in pages.iter().enumerate() {
+    for (i, page) in pages.iter().enumerate().skip(page_start).take(count) {
+        let favorite = favorites.iter().any(|favorite| favorite.id == page.id);
         execute!(
             stdout(),
             MoveTo(
                 x_center - (content_width / 2) + 10,
-                y_search + 9 + (i as u16)
+                y_search + 9 + ((i - page_start) as u16)
             ),
             SetForegroundColor(if i == page_pos {
                 Color::White
@@ -52,56 +286,87 @@ pub fn pages_view(
                 Color::DarkGrey
             }),
             SetBackgroundColor(if i == page_pos {
-                Color::Rgb {r: 49, g: 116, b: 143}
+                accent_color()
             } else {
                 Color::Reset
             }),
-            Print(format!(" {} ", page)),
+            Print(format!(" {} ", fit(&format!("{}{}", if favorite { "*" } else { "" }, page.title), page_width))),
             ResetColor
         )
         .unwrap();
     }
 
-    for (i, content) in contents.iter().enumerate() {
+    for (i, content) in contents.iter().enumerate().skip(content_start).take(count) {
+        let favorite = favorites.iter().any(|favorite| favorite.id == content.id);
         execute!(
             stdout(),
             MoveTo(
                 x_center - (content_width / 2) + 35,
-                y_search + 9 + (i as u16)
+                y_search + 9 + ((i - content_start) as u16)
             ),
             SetForegroundColor(if i == table_pos {
                 if i == 0 && !page_selected {
                     Color::DarkGrey
+                } else if favorite {
+                    Color::Black
                 } else {
-                    if favorites.contains(content) {
-                        Color::Black
-                    } else {
-                        Color::White
-                    }
+                    Color::White
                 }
             } else {
                 Color::DarkGrey
             }),
             SetBackgroundColor(if i == table_pos && page_selected {
-                if favorites.contains(content) {
-                    Color::Rgb {
-                        r: 252,
-                        g: 186,
-                        b: 3,
-                    }
+                if favorite {
+                    favorite_color()
                 } else {
-                    Color::Rgb {r: 66, g: 36, b: 156}
+                    secondary_color()
                 }
             } else {
                 Color::Reset
             }),
             Print(format!(
-                " {}{} ",
-                if favorites.contains(content) { "*" } else { "" },
-                content
+                " {} ",
+                fit(&format!("{}{}", if favorite { "*" } else { "" }, content.title), contents_width)
             )),
             ResetColor
         )
         .unwrap();
     }
 }
+
+// the new page or archive prompt, or the outcome of the last one, along the bottom
+pub fn pages_prompt(
+    content_width: &u16,
+    content_height: &u16,
+    x_center: &u16,
+    y_search: &u16,
+    prompt: Option<(String, &str)>,
+    message: Option<&str>,
+) {
+    let x = x_center - (content_width / 2) + 10;
+    let y = y_search + content_height - 5;
+
+    if let Some((question, hint)) = prompt {
+        write_ctrl(hint, x_center + (content_width / 2) - 10 - hint.chars().count() as u16, y_search + 7);
+
+        let line: String = question.chars().take((content_width - 20) as usize).collect();
+        execute!(
+            stdout(),
+            MoveTo(x, y),
+            SetForegroundColor(Color::White),
+            SetBackgroundColor(accent_color()),
+            Print(line),
+            ResetColor
+        )
+        .unwrap();
+    } else if let Some(message) = message {
+        execute!(
+            stdout(),
+            MoveTo(x, y),
+            SetForegroundColor(Color::DarkGrey),
+            Print(message.chars().take((content_width - 20) as usize).collect::<String>()),
+            ResetColor
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,362 @@
+use std::io::stdout;
+
+use crossterm::{
+    cursor::MoveTo,
+    execute,
+    style::{Color, ContentStyle, Print, ResetColor, SetBackgroundColor, SetForegroundColor, StyledContent, Stylize},
+};
+
+use crossterm::event::KeyEvent;
+
+use crate::{
+    api::{
+        blocks::{get_block_children, Block, RichText},
+        search::Object,
+    },
+    app::{Section, Workspace},
+    utils::{dimentions::get_dimensions, keymap::Action, theme::accent_color},
+    views::{Command, Layout, View},
+};
+
+type Line = Vec<StyledContent<String>>;
+
+pub struct Reader {
+    pub page: Object,
+    pub blocks: Vec<Block>,
+    pub scroll: usize,
+    pub message: Option<String>,
+    // outcome of the last edit, shown beside the title
+    pub status: Option<String>,
+    pub return_section: Section,
+}
+
+impl Reader {
+    pub fn new(page: Object, return_section: Section) -> Self {
+        Self {
+            page,
+            blocks: Vec::new(),
+            scroll: 0,
+            message: Some("loading...".to_string()),
+            status: None,
+            return_section,
+        }
+    }
+
+    pub async fn fetch(&mut self) {
+        match get_block_children(&self.page.id).await {
+            Ok(blocks) => {
+                self.blocks = blocks;
+                self.message = None;
+            }
+            Err(e) => self.message = Some(format!("failed to load page: {}", e)),
+        }
+    }
+
+    pub fn scroll_by(&mut self, delta: isize) {
+        let (content_width, content_height, _, _) = get_dimensions();
+        let lines = render_blocks(&self.blocks, reader_width(content_width));
+        let max_scroll = lines.len().saturating_sub(reader_height(content_height));
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max_scroll);
+    }
+
+    pub fn page_height(&self) -> isize {
+        let (_, content_height, _, _) = get_dimensions();
+        reader_height(content_height) as isize
+    }
+}
+
+impl View for Reader {
+    fn update(&mut self, action: Action, _key: &KeyEvent, _workspace: &mut Workspace) -> Command {
+        self.status = None;
+        match action {
+            Action::Edit if self.message.is_none() => return Command::Edit,
+            Action::Up => self.scroll_by(-1),
+            Action::Down => self.scroll_by(1),
+            Action::PageUp => self.scroll_by(-self.page_height()),
+            Action::PageDown => self.scroll_by(self.page_height()),
+            Action::Back => return Command::Goto(self.return_section),
+            _ => return Command::Unhandled,
+        }
+        Command::None
+    }
+
+    fn render(&self, layout: &Layout, _workspace: &Workspace) {
+        reader_view(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_search,
+            self,
+        );
+    }
+}
+
+fn reader_width(content_width: u16) -> usize {
+    content_width.saturating_sub(6) as usize
+}
+
+fn reader_height(content_height: u16) -> usize {
+    content_height.saturating_sub(9) as usize
+}
+
+fn plain(text: String) -> StyledContent<String> {
+    ContentStyle::new().apply(text)
+}
+
+fn span_style(rich_text: &RichText, base: ContentStyle) -> ContentStyle {
+    let mut style = base;
+    if rich_text.bold {
+        style = style.bold();
+    }
+    if rich_text.italic {
+        style = style.italic();
+    }
+    if rich_text.underline {
+        style = style.underlined();
+    }
+    if rich_text.strikethrough {
+        style = style.crossed_out();
+    }
+    if rich_text.code {
+        style = style.with(Color::Rgb { r: 235, g: 111, b: 146 });
+    }
+    if rich_text.href.is_some() {
+        style = style.with(accent_color()).underlined();
+    }
+    style
+}
+
+// split text into alternating runs of whitespace and non-whitespace
+fn tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::<String>::new();
+    for c in text.chars() {
+        match tokens.last_mut() {
+            Some(token) if token.ends_with(char::is_whitespace) == c.is_whitespace() => token.push(c),
+            _ => tokens.push(c.to_string()),
+        }
+    }
+    tokens
+}
+
+// word wrap styled spans behind a prefix, continuing with `continuation`
+fn wrap(
+    prefix: StyledContent<String>,
+    continuation: StyledContent<String>,
+    spans: &[(String, ContentStyle)],
+    width: usize,
+) -> Vec<Line> {
+    let available = width.saturating_sub(prefix.content().chars().count()).max(1);
+    let mut lines = Vec::<Line>::new();
+    let mut line: Line = vec![prefix];
+    let mut used = 0;
+
+    for (text, style) in spans {
+        for (i, segment) in text.split('\n').enumerate() {
+            if i > 0 {
+                lines.push(std::mem::replace(&mut line, vec![continuation.clone()]));
+                used = 0;
+            }
+
+            for token in tokens(segment) {
+                let blank = token.trim().is_empty();
+                let mut rest = token;
+                if used > 0 && used + rest.chars().count() > available {
+                    lines.push(std::mem::replace(&mut line, vec![continuation.clone()]));
+                    used = 0;
+                    if blank {
+                        continue;
+                    }
+                }
+
+                while rest.chars().count() > available {
+                    let head: String = rest.chars().take(available).collect();
+                    rest = rest.chars().skip(available).collect();
+                    line.push(style.apply(head));
+                    lines.push(std::mem::replace(&mut line, vec![continuation.clone()]));
+                }
+
+                used += rest.chars().count();
+                line.push(style.apply(rest));
+            }
+        }
+    }
+
+    lines.push(line);
+    lines
+}
+
+fn push_blocks(blocks: &[Block], depth: usize, width: usize, lines: &mut Vec<Line>) {
+    let indent = "  ".repeat(depth);
+    let mut number = 0;
+
+    for block in blocks {
+        number = if block.block_type == "numbered_list_item" { number + 1 } else { 0 };
+
+        let base = match block.block_type.as_str() {
+            "heading_1" => ContentStyle::new().with(Color::White).bold().underlined(),
+            "heading_2" | "heading_3" => ContentStyle::new().with(Color::White).bold(),
+            "quote" => ContentStyle::new().with(Color::White).italic(),
+            "callout" => ContentStyle::new().with(Color::Rgb { r: 252, g: 186, b: 3 }),
+            "code" => ContentStyle::new().with(Color::Rgb { r: 156, g: 207, b: 216 }),
+            "to_do" if block.checked == Some(true) => ContentStyle::new().with(Color::DarkGrey).crossed_out(),
+            "child_page" | "child_database" => ContentStyle::new().with(Color::DarkGrey).underlined(),
+            _ => ContentStyle::new().with(Color::White),
+        };
+        let spans: Vec<(String, ContentStyle)> = block
+            .text
+            .iter()
+            .map(|rich_text| (rich_text.text.clone(), span_style(rich_text, base)))
+            .collect();
+
+        let (marker, continuation) = match block.block_type.as_str() {
+            "bulleted_list_item" => ("• ".to_string(), "  ".to_string()),
+            "numbered_list_item" => {
+                let marker = format!("{}. ", number);
+                let continuation = " ".repeat(marker.chars().count());
+                (marker, continuation)
+            }
+            "to_do" => {
+                let marker = if block.checked == Some(true) { "[x] " } else { "[ ] " };
+                (marker.to_string(), "    ".to_string())
+            }
+            "toggle" => ("▸ ".to_string(), "  ".to_string()),
+            "quote" | "code" => ("│ ".to_string(), "│ ".to_string()),
+            "callout" => {
+                let icon = block.icon.clone().unwrap_or("!".to_string());
+                (format!("{} ", icon), "   ".to_string())
+            }
+            "child_page" => ("→ ".to_string(), "  ".to_string()),
+            "child_database" => ("⊞ ".to_string(), "  ".to_string()),
+            _ => (String::new(), String::new()),
+        };
+        let marker_style = ContentStyle::new().with(Color::DarkGrey);
+        let prefix = marker_style.apply(format!("{}{}", indent, marker));
+        let continuation = marker_style.apply(format!("{}{}", indent, continuation));
+
+        match block.block_type.as_str() {
+            "heading_1" | "heading_2" | "heading_3" => {
+                if !lines.is_empty() {
+                    lines.push(Vec::new());
+                }
+                lines.extend(wrap(prefix, continuation, &spans, width));
+            }
+            "divider" => {
+                lines.push(vec![
+                    plain(indent.clone()),
+                    marker_style.apply("─".repeat(width.saturating_sub(indent.len()))),
+                ]);
+            }
+            "code" => {
+                lines.push(vec![marker_style.apply(format!(
+                    "{}{}",
+                    indent,
+                    block.language.clone().unwrap_or_default()
+                ))]);
+                lines.extend(wrap(prefix, continuation, &spans, width));
+            }
+            "paragraph" | "bulleted_list_item" | "numbered_list_item" | "to_do"
+            | "toggle" | "quote" | "callout" | "child_page" | "child_database" => {
+                lines.extend(wrap(prefix, continuation, &spans, width));
+            }
+            other => {
+                lines.push(vec![marker_style.apply(format!("{}[{}]", indent, other))]);
+            }
+        }
+
+        push_blocks(&block.children, depth + 1, width, lines);
+    }
+}
+
+pub fn render_blocks(blocks: &[Block], width: usize) -> Vec<Line> {
+    let mut lines = Vec::<Line>::new();
+    push_blocks(blocks, 0, width, &mut lines);
+    lines
+}
+
+pub fn reader_view(
+    content_width: &u16,
+    content_height: &u16,
+    x_center: &u16,
+    y_search: &u16,
+    reader: &Reader,
+) {
+    for i in 2..content_height - 4 {
+        execute!(
+            stdout(),
+            MoveTo(x_center - (content_width / 2) + 1, y_search + i + 1),
+            Print(" ".repeat((content_width - 2) as usize)),
+        )
+        .unwrap();
+    }
+
+    let width = reader_width(*content_width);
+    let height = reader_height(*content_height);
+    let x_reader = x_center - (content_width / 2) + 3;
+
+    let title: String = reader.page.title.chars().take(width.saturating_sub(2)).collect();
+    execute!(
+        stdout(),
+        MoveTo(x_reader, y_search + 4),
+        SetBackgroundColor(Color::White),
+        SetForegroundColor(Color::Black),
+        Print(format!(" {} ", title)),
+        ResetColor
+    )
+    .unwrap();
+
+    if let Some(status) = &reader.status {
+        let status: String = status.chars().take(width.saturating_sub(title.chars().count() + 4)).collect();
+        execute!(
+            stdout(),
+            MoveTo(x_reader + width as u16 - status.chars().count() as u16, y_search + 4),
+            SetForegroundColor(Color::DarkGrey),
+            Print(status),
+            ResetColor
+        )
+        .unwrap();
+    }
+
+    if let Some(message) = &reader.message {
+        execute!(
+            stdout(),
+            MoveTo(x_reader, y_search + 6),
+            SetForegroundColor(Color::DarkGrey),
+            Print(message),
+            ResetColor
+        )
+        .unwrap();
+        return;
+    }
+
+    let lines = render_blocks(&reader.blocks, width);
+    for (i, line) in lines.iter().skip(reader.scroll).take(height).enumerate() {
+        execute!(stdout(), MoveTo(x_reader, y_search + 6 + i as u16)).unwrap();
+        for span in line {
+            execute!(stdout(), Print(span)).unwrap();
+        }
+        execute!(stdout(), ResetColor).unwrap();
+    }
+
+    // scroll markers
+    let x_marker = x_center + (content_width / 2) - 2;
+    if reader.scroll > 0 {
+        execute!(
+            stdout(),
+            MoveTo(x_marker, y_search + 6),
+            SetForegroundColor(Color::DarkGrey),
+            Print("↑"),
+            ResetColor
+        )
+        .unwrap();
+    }
+    if reader.scroll + height < lines.len() {
+        execute!(
+            stdout(),
+            MoveTo(x_marker, y_search + 5 + height as u16),
+            SetForegroundColor(Color::DarkGrey),
+            Print("↓"),
+            ResetColor
+        )
+        .unwrap();
+    }
+}
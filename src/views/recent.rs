@@ -0,0 +1,174 @@
+use std::io::stdout;
+
+use crossterm::{
+    cursor::MoveTo,
+    execute,
+    style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
+};
+
+use crossterm::event::KeyEvent;
+
+use crate::{
+    api::search::{flatten_tree, Object},
+    app::Workspace,
+    database::recents::{get_recents, merge_edited, Recent},
+    utils::{controls::write_ctrl, keymap::Action, theme::accent_color, time::relative_time},
+    views::{wrap_pos, Command, Layout, View},
+};
+
+const RECENTS_LIMIT: usize = 50;
+
+#[derive(Default)]
+pub struct Recents {
+    pub items: Vec<Recent>,
+    pub pos: usize,
+    pub merged: bool,
+}
+
+impl Recents {
+    // reload from the database, optionally merging notion's edit times
+    pub fn load(&mut self, tree: &[Object]) {
+        let recents = get_recents(RECENTS_LIMIT).unwrap_or_default();
+        self.items = if self.merged {
+            merge_edited(recents, &flatten_tree(tree), RECENTS_LIMIT)
+        } else {
+            recents
+        };
+        self.pos = self.pos.min(self.items.len().saturating_sub(1));
+    }
+
+    pub fn selected(&self) -> Option<&Recent> {
+        self.items.get(self.pos)
+    }
+}
+
+impl View for Recents {
+    fn update(&mut self, action: Action, _key: &KeyEvent, workspace: &mut Workspace) -> Command {
+        match action {
+            Action::Up | Action::Down => {
+                let delta = if action == Action::Up { -1 } else { 1 };
+                self.pos = wrap_pos(self.pos, delta, self.items.len());
+            }
+            Action::Open => {
+                if let Some(recent) = self.selected() {
+                    return Command::Open(recent.to_object());
+                }
+            }
+            Action::ToggleEdits => {
+                self.merged = !self.merged;
+                self.pos = 0;
+                self.load(&workspace.tree);
+            }
+            _ => return Command::Unhandled,
+        }
+        Command::None
+    }
+
+    fn enter(&mut self, workspace: &Workspace) {
+        self.pos = 0;
+        self.load(&workspace.tree);
+    }
+
+    fn render(&self, layout: &Layout, _workspace: &Workspace) {
+        recents_view(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_search,
+            self,
+        );
+    }
+}
+
+pub fn recents_view(
+    content_width: &u16,
+    content_height: &u16,
+    x_center: &u16,
+    y_search: &u16,
+    recents: &Recents,
+) {
+    for i in 2..content_height - 4 {
+        execute!(
+            stdout(),
+            MoveTo(x_center - (content_width / 2) + 1, y_search + i + 1),
+            Print(" ".repeat((content_width - 2) as usize)),
+        )
+        .unwrap();
+    }
+
+    execute!(
+        stdout(),
+        MoveTo(x_center - (content_width / 2) + 10, y_search + 7),
+        SetBackgroundColor(Color::White),
+        SetForegroundColor(Color::Black),
+        Print(" browse recents ".to_string()),
+        ResetColor
+    )
+    .unwrap();
+
+    let recents_pos = recents.pos;
+    let toggle = if recents.merged {
+        "[e] hide notion edits"
+    } else {
+        "[e] show notion edits"
+    };
+    write_ctrl(
+        toggle,
+        x_center + (content_width / 2) - 10 - toggle.len() as u16,
+        y_search + 7,
+    );
+
+    let width = (content_width - 20) as usize;
+    let count = 7;
+    let start = count * (recents_pos / count);
+    let end = if start + count > recents.items.len() {
+        recents.items.len()
+    } else {
+        start + count
+    };
+    for (i, recent) in recents.items[start..end].iter().enumerate() {
+        let age = format!(
+            "{} {}",
+            if recent.edited { "edited" } else { "opened" },
+            relative_time(recent.accessed_at)
+        );
+        let title: String = recent
+            .title
+            .chars()
+            .take(width.saturating_sub(age.len() + 3))
+            .collect();
+        let gap = width.saturating_sub(title.chars().count() + age.len() + 2);
+
+        execute!(
+            stdout(),
+            MoveTo(
+                x_center - (content_width / 2) + 10,
+                y_search + 9 + (i as u16)
+            ),
+            SetForegroundColor(if i == (recents_pos % count) {
+                Color::White
+            } else {
+                Color::DarkGrey
+            }),
+            SetBackgroundColor(if i == (recents_pos % count) {
+                accent_color()
+            } else {
+                Color::Reset
+            }),
+            Print(format!(" {}{}{} ", title, " ".repeat(gap), age)),
+            ResetColor
+        )
+        .unwrap();
+    }
+
+    if recents.items.is_empty() {
+        execute!(
+            stdout(),
+            MoveTo(x_center - (content_width / 2) + 10, y_search + 9),
+            SetForegroundColor(Color::DarkGrey),
+            Print("pages and tables you open show up here"),
+            ResetColor
+        )
+        .unwrap();
+    }
+}
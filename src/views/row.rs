@@ -0,0 +1,419 @@
+use std::io::stdout;
+
+use crossterm::{
+    cursor::MoveTo,
+    execute,
+    style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use serde_json::Value;
+
+use crate::{
+    api::{
+        databases::{coerce_items, coerce_property, empty_property, is_writable, Column, Row},
+        search::Object,
+        users::Person,
+    },
+    app::{Section, Workspace},
+    utils::{controls::write_ctrl, keymap::Action, theme::accent_color},
+    views::{wrap_pos, Command, Layout, View},
+};
+
+// how a property is being changed
+enum Input {
+    Text(String),
+    // options with whether each is chosen, only one at a time unless `multi`
+    Pick {
+        options: Vec<String>,
+        chosen: Vec<bool>,
+        pos: usize,
+        multi: bool,
+    },
+}
+
+// one database row's properties as a form
+pub struct RowEditor {
+    pub database: Object,
+    pub row: Row,
+    pub title: String,
+    pub columns: Vec<Column>,
+    pub values: Vec<String>,
+    pub people: Vec<Person>,
+    pub pos: usize,
+    input: Option<Input>,
+    // the property shown changed before notion confirmed it, with the text to go back to
+    pending: Option<(usize, String)>,
+    pub status: Option<String>,
+    pub return_section: Section,
+}
+
+impl RowEditor {
+    pub fn new(
+        database: Object,
+        columns: Vec<Column>,
+        row: Row,
+        values: Vec<String>,
+        people: Vec<Person>,
+        return_section: Section,
+    ) -> Self {
+        Self {
+            database,
+            title: values.first().cloned().unwrap_or_default(),
+            columns,
+            row,
+            values,
+            people,
+            pos: 0,
+            input: None,
+            pending: None,
+            status: None,
+            return_section,
+        }
+    }
+
+    pub fn page(&self) -> Object {
+        Object {
+            id: self.row.id.clone(),
+            parent_id: Some(self.database.id.clone()),
+            title: self.title.clone(),
+            object_type: "page".to_string(),
+            children: Vec::new(),
+            last_edited_time: None,
+        }
+    }
+
+    // notion accepted the change, take its version of the row
+    pub fn confirm(&mut self, properties: Value, values: Vec<String>) {
+        self.row.properties = properties;
+        self.title = values.first().cloned().unwrap_or_default();
+        self.values = values;
+        self.pending = None;
+        self.status = Some("saved".to_string());
+    }
+
+    // notion refused the change, put the old value back
+    pub fn rollback(&mut self, error: String) {
+        if let Some((index, previous)) = self.pending.take() {
+            self.values[index] = previous;
+            self.status = Some(format!("{} not saved, {}", self.columns[index].name, error));
+        }
+    }
+
+    // what the row holds now, option names or people ids, read from notion's json
+    // rather than the display text so names with commas stay whole
+    fn chosen(&self, column: &Column) -> Vec<String> {
+        let value = &self.row.properties[&column.name][&column.property_type];
+        let (items, key) = match column.property_type.as_str() {
+            "people" => (value.as_array().cloned().unwrap_or_default(), "id"),
+            "multi_select" => (value.as_array().cloned().unwrap_or_default(), "name"),
+            _ => (vec![value.clone()], "name"),
+        };
+        items
+            .iter()
+            .filter_map(|item| item[key].as_str())
+            .map(|item| item.to_string())
+            .collect()
+    }
+
+    // checkboxes flip straight away, choices open a picker and the rest are typed
+    fn begin(&mut self) -> Command {
+        let Some(column) = self.columns.get(self.pos).cloned() else {
+            return Command::None;
+        };
+        if !is_writable(&column.property_type) {
+            self.status = Some(format!("{} properties are computed by notion", column.property_type));
+            return Command::None;
+        }
+
+        let current = &self.values[self.pos];
+        self.input = Some(match column.property_type.as_str() {
+            "checkbox" => {
+                let raw = if current == "[x]" { "false" } else { "true" };
+                return self.commit(raw.to_string());
+            }
+            "select" | "status" | "multi_select" => {
+                let selected = self.chosen(&column);
+                Input::Pick {
+                    chosen: column.options.iter().map(|option| selected.contains(option)).collect(),
+                    options: column.options.clone(),
+                    pos: 0,
+                    multi: column.property_type == "multi_select",
+                }
+            }
+            "people" => {
+                let ids = self.chosen(&column);
+                Input::Pick {
+                    options: self.people.iter().map(|person| person.name.clone()).collect(),
+                    chosen: self.people.iter().map(|person| ids.contains(&person.id)).collect(),
+                    pos: 0,
+                    multi: true,
+                }
+            }
+            // relations show titles but are written as page ids
+            "relation" => Input::Text(
+                self.row.properties[&column.name]["relation"]
+                    .as_array()
+                    .map(|pages| {
+                        pages
+                            .iter()
+                            .filter_map(|page| page["id"].as_str())
+                            .collect::<Vec<&str>>()
+                            .join(", ")
+                    })
+                    .unwrap_or_default(),
+            ),
+            _ => Input::Text(current.clone()),
+        });
+        Command::None
+    }
+
+    // typed text, coerced the way a csv cell would be
+    fn commit(&mut self, raw: String) -> Command {
+        let column = &self.columns[self.pos];
+        let value = coerce_property(column, &raw, &self.people);
+        let shown = match &value {
+            Ok(Some(value)) if column.property_type == "checkbox" && value["checkbox"] == true => "[x]".to_string(),
+            _ if column.property_type == "checkbox" => "[ ]".to_string(),
+            _ => raw.trim().to_string(),
+        };
+        self.send(value, shown)
+    }
+
+    // the picked options go over as a list, people by id
+    fn commit_picked(&mut self, options: &[String], chosen: &[bool]) -> Command {
+        let column = &self.columns[self.pos];
+        let picked: Vec<(&str, &String)> = options
+            .iter()
+            .zip(chosen)
+            .enumerate()
+            .filter(|(_, (_, chosen))| **chosen)
+            .map(|(i, (option, _))| match column.property_type.as_str() {
+                "people" => (self.people[i].id.as_str(), option),
+                _ => (option.as_str(), option),
+            })
+            .collect();
+        let items: Vec<&str> = picked.iter().map(|(item, _)| *item).collect();
+        let value = coerce_items(column, &items, &self.people);
+        let shown = picked.iter().map(|(_, name)| name.as_str()).collect::<Vec<&str>>().join(", ");
+        self.send(value, shown)
+    }
+
+    // show the new value right away and ask the app to send it
+    fn send(&mut self, value: Result<Option<Value>, String>, shown: String) -> Command {
+        let column = &self.columns[self.pos];
+        let value = match value {
+            Ok(Some(value)) => value,
+            Ok(None) => empty_property(column),
+            Err(error) => {
+                self.status = Some(error);
+                return Command::None;
+            }
+        };
+
+        self.input = None;
+        let previous = std::mem::replace(&mut self.values[self.pos], shown);
+        self.pending = Some((self.pos, previous));
+        self.status = Some("saving...".to_string());
+        Command::SetProperty(self.row.id.clone(), column.name.clone(), value)
+    }
+}
+
+impl View for RowEditor {
+    fn update(&mut self, action: Action, key: &KeyEvent, _workspace: &mut Workspace) -> Command {
+        self.status = None;
+        match self.input.as_mut() {
+            Some(Input::Text(text)) => match action {
+                Action::Type => {
+                    if let KeyCode::Char(c) = key.code {
+                        text.push(c);
+                    }
+                }
+                Action::Erase => {
+                    text.pop();
+                }
+                Action::Open => {
+                    let raw = text.clone();
+                    return self.commit(raw);
+                }
+                Action::Back => self.input = None,
+                _ => return Command::Unhandled,
+            },
+            Some(Input::Pick { options, chosen, pos, multi }) => match action {
+                Action::Up | Action::Down => {
+                    let delta = if action == Action::Up { -1 } else { 1 };
+                    *pos = wrap_pos(*pos, delta, options.len());
+                }
+                Action::Toggle if *multi && *pos < chosen.len() => chosen[*pos] = !chosen[*pos],
+                Action::Open => {
+                    // enter on a single choice always picks it
+                    if !*multi {
+                        for (i, option) in chosen.iter_mut().enumerate() {
+                            *option = i == *pos;
+                        }
+                    }
+                    let (options, chosen) = (options.clone(), chosen.clone());
+                    return self.commit_picked(&options, &chosen);
+                }
+                Action::Back => self.input = None,
+                _ => return Command::Unhandled,
+            },
+            None => match action {
+                Action::Up | Action::Down => {
+                    let delta = if action == Action::Up { -1 } else { 1 };
+                    self.pos = wrap_pos(self.pos, delta, self.columns.len());
+                }
+                Action::Open => return self.begin(),
+                Action::OpenPage => return Command::Open(self.page()),
+                Action::Back => return Command::Goto(self.return_section),
+                _ => return Command::Unhandled,
+            },
+        }
+        Command::None
+    }
+
+    fn editing(&self) -> bool {
+        matches!(self.input, Some(Input::Text(_)))
+    }
+
+    fn render(&self, layout: &Layout, _workspace: &Workspace) {
+        row_view(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_search,
+            self,
+        );
+    }
+}
+
+pub fn row_view(
+    content_width: &u16,
+    content_height: &u16,
+    x_center: &u16,
+    y_search: &u16,
+    editor: &RowEditor,
+) {
+    for i in 2..content_height - 4 {
+        execute!(
+            stdout(),
+            MoveTo(x_center - (content_width / 2) + 1, y_search + i + 1),
+            Print(" ".repeat((content_width - 2) as usize)),
+        )
+        .unwrap();
+    }
+
+    let width = content_width.saturating_sub(6) as usize;
+    let x_row = x_center - (content_width / 2) + 3;
+
+    let title: String = format!("{} / {}", editor.database.title, editor.title)
+        .chars()
+        .take(width.saturating_sub(2))
+        .collect();
+    execute!(
+        stdout(),
+        MoveTo(x_row, y_search + 4),
+        SetBackgroundColor(Color::White),
+        SetForegroundColor(Color::Black),
+        Print(format!(" {} ", title)),
+        ResetColor
+    )
+    .unwrap();
+
+    if let Some(status) = &editor.status {
+        let status: String = status.chars().take(width.saturating_sub(title.chars().count() + 4)).collect();
+        execute!(
+            stdout(),
+            MoveTo(x_row + width as u16 - status.chars().count() as u16, y_search + 4),
+            SetForegroundColor(Color::DarkGrey),
+            Print(status),
+            ResetColor
+        )
+        .unwrap();
+    }
+
+    let hint = match &editor.input {
+        Some(Input::Text(_)) => "[enter] save  [esc] cancel",
+        Some(Input::Pick { multi: true, .. }) => "[space] toggle  [enter] save  [esc] cancel",
+        Some(Input::Pick { .. }) => "[enter] pick  [esc] cancel",
+        None => "[enter] edit  [o] open page",
+    };
+    write_ctrl(hint, x_row + width as u16 - hint.len() as u16, y_search + content_height - 5);
+
+    let label_width = editor
+        .columns
+        .iter()
+        .map(|column| column.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(width / 3);
+    let value_width = width.saturating_sub(label_width + 3);
+    let count = (*content_height as usize).saturating_sub(12).max(1);
+    let start = count * (editor.pos / count);
+
+    for (i, column) in editor.columns.iter().enumerate().skip(start).take(count) {
+        let selected = i == editor.pos;
+        let label: String = column.name.chars().take(label_width).collect();
+        let value = match (&editor.input, selected) {
+            (Some(Input::Text(text)), true) => format!("{}_", text),
+            _ => editor.values[i].clone(),
+        };
+        let value: String = value.chars().take(value_width).collect();
+        let y = y_search + 6 + (i - start) as u16;
+
+        execute!(
+            stdout(),
+            MoveTo(x_row, y),
+            SetForegroundColor(Color::DarkGrey),
+            Print(format!("{:width$}   ", label, width = label_width)),
+            SetForegroundColor(if selected {
+                Color::White
+            } else if is_writable(&column.property_type) {
+                Color::Grey
+            } else {
+                Color::DarkGrey
+            }),
+            SetBackgroundColor(if selected { accent_color() } else { Color::Reset }),
+            Print(format!(" {} ", value)),
+            ResetColor
+        )
+        .unwrap();
+    }
+
+    // the options open beside the property being picked
+    if let Some(Input::Pick { options, chosen, pos, multi }) = &editor.input {
+        let x_options = x_row + (label_width + 3) as u16 + (value_width / 2) as u16;
+        let y_options = y_search + 6 + (editor.pos - start) as u16 + 1;
+        let room = (y_search + content_height).saturating_sub(y_options + 6) as usize;
+        let shown = room.max(1);
+        let first = shown * (pos / shown);
+        if options.is_empty() {
+            execute!(
+                stdout(),
+                MoveTo(x_options, y_options),
+                SetForegroundColor(Color::DarkGrey),
+                Print(" no options "),
+                ResetColor
+            )
+            .unwrap();
+        }
+        for (i, option) in options.iter().enumerate().skip(first).take(shown) {
+            let mark = match (multi, chosen[i]) {
+                (true, true) => "[x] ",
+                (true, false) => "[ ] ",
+                (false, true) => "• ",
+                (false, false) => "  ",
+            };
+            let line: String = format!(" {}{} ", mark, option).chars().take(value_width / 2).collect();
+            execute!(
+                stdout(),
+                MoveTo(x_options, y_options + (i - first) as u16),
+                SetForegroundColor(if i == *pos { Color::White } else { Color::Grey }),
+                SetBackgroundColor(if i == *pos { accent_color() } else { Color::Black }),
+                Print(line),
+                ResetColor
+            )
+            .unwrap();
+        }
+    }
+}
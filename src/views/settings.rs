@@ -0,0 +1,253 @@
+use std::io::stdout;
+
+use crossterm::{
+    cursor::MoveTo,
+    execute,
+    style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
+};
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    api::journal::valid_title_format,
+    app::Workspace,
+    utils::{
+        config::{config_error, get_config, save_config, Config, START_SECTIONS},
+        controls::write_ctrl,
+        keymap::Action,
+        theme::{accent_color, THEMES},
+    },
+    views::{wrap_pos, Command, Layout, View},
+};
+
+const FIELDS: [&str; 6] = [
+    "database location",
+    "notion version",
+    "search page size",
+    "journal title",
+    "start section",
+    "theme",
+];
+
+#[derive(Default)]
+pub struct Settings {
+    pub config: Config,
+    pub pos: usize,
+    pub editing: Option<String>,
+    pub message: Option<String>,
+}
+
+impl Settings {
+    pub fn load(&mut self) {
+        self.config = get_config();
+        self.editing = None;
+        self.message = config_error();
+    }
+
+    // start section and theme cycle through fixed choices, the rest are typed in
+    pub fn is_choice(&self) -> bool {
+        self.pos >= 4
+    }
+
+    fn value(&self, field: usize) -> String {
+        match field {
+            0 => self.config.db_path.clone(),
+            1 => self.config.notion_version.clone(),
+            2 => self.config.search_page_size.to_string(),
+            3 => self.config.journal_title.clone(),
+            4 => self.config.start_section.clone(),
+            _ => self.config.theme.clone(),
+        }
+    }
+
+    pub fn cycle(&mut self, delta: isize) {
+        let (choices, current): (&[&str], &mut String) = match self.pos {
+            4 => (&START_SECTIONS, &mut self.config.start_section),
+            5 => (&THEMES, &mut self.config.theme),
+            _ => return,
+        };
+        let index = choices.iter().position(|choice| choice == current).unwrap_or(0);
+        *current = choices[(index as isize + delta).rem_euclid(choices.len() as isize) as usize].to_string();
+        self.message = None;
+        self.save();
+    }
+
+    pub fn begin_edit(&mut self) {
+        self.editing = Some(self.value(self.pos));
+        self.message = None;
+    }
+
+    pub fn commit_edit(&mut self) {
+        let value = match self.editing.take() {
+            Some(value) => value.trim().to_string(),
+            None => return,
+        };
+        if value.is_empty() {
+            return;
+        }
+
+        match self.pos {
+            0 => {
+                if value != self.config.db_path {
+                    self.config.db_path = value;
+                    self.message = Some("restart to use the new database location".to_string());
+                }
+            }
+            1 => self.config.notion_version = value,
+            3 if valid_title_format(&value) => self.config.journal_title = value,
+            3 => {
+                self.message = Some("journal title must be a strftime pattern like %Y-%m-%d".to_string());
+                return;
+            }
+            _ => match value.parse::<usize>() {
+                Ok(size) if (1..=100).contains(&size) => self.config.search_page_size = size,
+                _ => {
+                    self.message = Some("page size must be between 1 and 100".to_string());
+                    return;
+                }
+            },
+        }
+        self.save();
+    }
+
+    fn save(&mut self) {
+        if let Err(e) = save_config(self.config.clone()) {
+            self.message = Some(e.to_string());
+        }
+    }
+}
+
+impl View for Settings {
+    fn update(&mut self, action: Action, key: &KeyEvent, _workspace: &mut Workspace) -> Command {
+        match action {
+            Action::Up | Action::Down => {
+                let delta = if action == Action::Up { -1 } else { 1 };
+                self.pos = wrap_pos(self.pos, delta, FIELDS.len());
+                self.editing = None;
+            }
+            Action::Open if self.editing.is_some() => self.commit_edit(),
+            // a new theme recolors the whole screen
+            Action::Open | Action::Right if self.is_choice() => {
+                self.cycle(1);
+                return Command::Redraw;
+            }
+            Action::Left if self.is_choice() => {
+                self.cycle(-1);
+                return Command::Redraw;
+            }
+            Action::Open => self.begin_edit(),
+            Action::Type => {
+                if let (Some(input), KeyCode::Char(c)) = (self.editing.as_mut(), key.code) {
+                    input.push(c);
+                }
+            }
+            Action::Erase => {
+                if let Some(input) = self.editing.as_mut() {
+                    input.pop();
+                }
+            }
+            Action::Back if self.editing.is_some() => self.editing = None,
+            _ => return Command::Unhandled,
+        }
+        Command::None
+    }
+
+    fn editing(&self) -> bool {
+        self.editing.is_some()
+    }
+
+    fn enter(&mut self, _workspace: &Workspace) {
+        self.load();
+    }
+
+    fn render(&self, layout: &Layout, _workspace: &Workspace) {
+        settings_view(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_search,
+            self,
+        );
+    }
+}
+
+pub fn settings_view(
+    content_width: &u16,
+    content_height: &u16,
+    x_center: &u16,
+    y_search: &u16,
+    settings: &Settings,
+) {
+    for i in 2..content_height - 4 {
+        execute!(
+            stdout(),
+            MoveTo(x_center - (content_width / 2) + 1, y_search + i + 1),
+            Print(" ".repeat((content_width - 2) as usize)),
+        )
+        .unwrap();
+    }
+
+    execute!(
+        stdout(),
+        MoveTo(x_center - (content_width / 2) + 10, y_search + 7),
+        SetBackgroundColor(Color::White),
+        SetForegroundColor(Color::Black),
+        Print(" settings ".to_string()),
+        ResetColor
+    )
+    .unwrap();
+
+    let hint = if settings.editing.is_some() {
+        "[enter] save  [esc] cancel"
+    } else {
+        "[enter] edit  [←/→] cycle"
+    };
+    write_ctrl(
+        hint,
+        x_center + (content_width / 2) - 10 - hint.chars().count() as u16,
+        y_search + 7,
+    );
+
+    let label_width = FIELDS.iter().map(|field| field.len()).max().unwrap_or(0);
+    let width = (content_width - 20) as usize;
+    for (i, field) in FIELDS.iter().enumerate() {
+        let selected = i == settings.pos;
+        let value = match &settings.editing {
+            Some(input) if selected => format!("{}_", input),
+            _ if selected && settings.is_choice() => format!("< {} >", settings.value(i)),
+            _ => settings.value(i),
+        };
+        let line: String = format!(" {:<label_width$}   {}", field, value)
+            .chars()
+            .take(width)
+            .collect();
+        let gap = width.saturating_sub(line.chars().count());
+
+        execute!(
+            stdout(),
+            MoveTo(
+                x_center - (content_width / 2) + 10,
+                y_search + 9 + (i as u16)
+            ),
+            SetForegroundColor(if selected { Color::White } else { Color::DarkGrey }),
+            SetBackgroundColor(if selected { accent_color() } else { Color::Reset }),
+            Print(format!("{}{}", line, " ".repeat(gap))),
+            ResetColor
+        )
+        .unwrap();
+    }
+
+    if let Some(message) = &settings.message {
+        execute!(
+            stdout(),
+            MoveTo(
+                x_center - (content_width / 2) + 10,
+                y_search + 10 + FIELDS.len() as u16
+            ),
+            SetForegroundColor(Color::DarkGrey),
+            Print(message),
+            ResetColor
+        )
+        .unwrap();
+    }
+}
@@ -6,14 +6,88 @@ use crossterm::{
     style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
 };
 
+use crossterm::event::KeyEvent;
+
+use crate::{
+    api::search::Object,
+    app::Workspace,
+    database::favorites::{toggle_favorite, Favorite},
+    utils::{
+        keymap::Action,
+        theme::{accent_color, favorite_color},
+    },
+    views::{pages::pages_prompt, wrap_pos, Command, Layout, View},
+};
+
+#[derive(Default)]
+pub struct TablesView {
+    pub pos: usize,
+    pub message: Option<String>,
+}
+
+impl View for TablesView {
+    fn update(&mut self, action: Action, _key: &KeyEvent, workspace: &mut Workspace) -> Command {
+        let tables = &workspace.tables;
+        match action {
+            Action::Up | Action::Down => {
+                let delta = if action == Action::Up { -1 } else { 1 };
+                self.pos = wrap_pos(self.pos, delta, tables.len());
+                Command::None
+            }
+            Action::Open => tables
+                .get(self.pos)
+                .map_or(Command::None, |table| Command::Open(table.clone())),
+            Action::Board => tables
+                .get(self.pos)
+                .map_or(Command::None, |table| Command::Board(table.clone())),
+            Action::Calendar => tables
+                .get(self.pos)
+                .map_or(Command::None, |table| Command::Calendar(table.clone())),
+            Action::Favorite => {
+                if let Some(table) = tables.get(self.pos) {
+                    toggle_favorite(table);
+                }
+                workspace.reload_favorites();
+                Command::None
+            }
+            _ => Command::Unhandled,
+        }
+    }
+
+    fn enter(&mut self, _workspace: &Workspace) {
+        self.pos = 0;
+        self.message = None;
+    }
+
+    fn render(&self, layout: &Layout, workspace: &Workspace) {
+        tables_view(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_search,
+            &workspace.tables,
+            self.pos,
+            &workspace.favorites,
+        );
+        pages_prompt(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_search,
+            None,
+            self.message.as_deref(),
+        );
+    }
+}
+
 pub fn tables_view(
     content_width: &u16,
     content_height: &u16,
     x_center: &u16,
     y_search: &u16,
-    tables: &Vec<&str>,
+    tables: &[Object],
     table_pos: usize,
-    favorites: &Vec<&str>,
+    favorites: &[Favorite],
 ) {
     for i in 2..content_height - 4 {
         execute!(
@@ -42,6 +116,7 @@ pub fn tables_view(
         start + count
     };
     for (i, table) in tables[start..end].iter().enumerate() {
+        let favorite = favorites.iter().any(|favorite| favorite.id == table.id);
         execute!(
             stdout(),
             MoveTo(
@@ -49,7 +124,7 @@ pub fn tables_view(
                 y_search + 9 + (i as u16)
             ),
             SetForegroundColor(if i == (table_pos%count) {
-                if favorites.contains(table) {
+                if favorite {
                     Color::Black
                 } else {
                     Color::White
@@ -58,18 +133,18 @@ pub fn tables_view(
                 Color::DarkGrey
             }),
             SetBackgroundColor(if i == (table_pos%count) {
-                if favorites.contains(table) {
-                    Color::Rgb { r: 252, g: 186, b: 3 }
+                if favorite {
+                    favorite_color()
                 } else {
-                    Color::Rgb {r: 49, g: 116, b: 143}
+                    accent_color()
                 }
             } else {
                 Color::Reset
             }),
             Print(format!(
                 " {}{} ",
-                if favorites.contains(table) { "*" } else { "" },
-                table
+                if favorite { "*" } else { "" },
+                table.title
             )),
             ResetColor
         )
@@ -0,0 +1,163 @@
+use std::io::stdout;
+
+use crossterm::{
+    cursor::MoveTo,
+    execute,
+    style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor, Stylize},
+};
+
+use crossterm::event::KeyEvent;
+
+use crate::{
+    api::todos::Todo,
+    app::Workspace,
+    utils::{controls::write_ctrl, keymap::Action, theme::accent_color},
+    views::{wrap_pos, Command, Layout, View},
+};
+
+// open to-dos from the todo sources, in the order the sources are listed
+#[derive(Default)]
+pub struct TodosView {
+    pub todos: Vec<Todo>,
+    pub pos: usize,
+    pub message: Option<String>,
+}
+
+impl TodosView {
+    pub fn load(&mut self, todos: Vec<Todo>) {
+        self.todos = todos;
+        self.pos = self.pos.min(self.todos.len().saturating_sub(1));
+    }
+
+    // drop a to-do that was checked off
+    pub fn remove(&mut self, id: &str) {
+        self.todos.retain(|todo| todo.id != id);
+        self.pos = self.pos.min(self.todos.len().saturating_sub(1));
+    }
+}
+
+impl View for TodosView {
+    fn update(&mut self, action: Action, _key: &KeyEvent, _workspace: &mut Workspace) -> Command {
+        self.message = None;
+        match action {
+            Action::Up | Action::Down => {
+                let delta = if action == Action::Up { -1 } else { 1 };
+                self.pos = wrap_pos(self.pos, delta, self.todos.len());
+            }
+            Action::Toggle => {
+                if let Some(todo) = self.todos.get(self.pos) {
+                    self.message = Some(format!("checking off {}...", todo.text));
+                    return Command::Check(todo.clone());
+                }
+            }
+            Action::Open => {
+                if let Some(todo) = self.todos.get(self.pos) {
+                    return Command::Open(todo.page());
+                }
+            }
+            _ => return Command::Unhandled,
+        }
+        Command::None
+    }
+
+    fn render(&self, layout: &Layout, _workspace: &Workspace) {
+        todos_view(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_search,
+            self,
+        );
+    }
+}
+
+pub fn todos_view(
+    content_width: &u16,
+    content_height: &u16,
+    x_center: &u16,
+    y_search: &u16,
+    todos: &TodosView,
+) {
+    for i in 2..content_height - 4 {
+        execute!(
+            stdout(),
+            MoveTo(x_center - (content_width / 2) + 1, y_search + i + 1),
+            Print(" ".repeat((content_width - 2) as usize)),
+        )
+        .unwrap();
+    }
+
+    let x_list = x_center - (content_width / 2) + 10;
+    execute!(
+        stdout(),
+        MoveTo(x_list, y_search + 7),
+        SetBackgroundColor(Color::White),
+        SetForegroundColor(Color::Black),
+        Print(" todos ".to_string()),
+        ResetColor
+    )
+    .unwrap();
+
+    let hint = "[space] check off  [enter] open";
+    write_ctrl(hint, x_center + (content_width / 2) - 10 - hint.len() as u16, y_search + 7);
+
+    // a heading before each source's to-dos, none for the to-do index
+    let mut lines: Vec<(&str, Option<usize>)> = Vec::new();
+    for (i, todo) in todos.todos.iter().enumerate() {
+        if i == 0 || todos.todos[i - 1].source.id != todo.source.id {
+            lines.push((&todo.source.title, None));
+        }
+        lines.push((&todo.text, Some(i)));
+    }
+
+    let width = (content_width - 20) as usize;
+    let count = (*content_height as usize).saturating_sub(16).max(1);
+    let selected = lines.iter().position(|&(_, todo)| todo == Some(todos.pos)).unwrap_or(0);
+    let start = count * (selected / count);
+    for (i, &(text, todo)) in lines.iter().enumerate().skip(start).take(count) {
+        let y = y_search + 9 + (i - start) as u16;
+        match todo {
+            None => {
+                let heading: String = text.chars().take(width).collect();
+                execute!(
+                    stdout(),
+                    MoveTo(x_list, y),
+                    SetForegroundColor(Color::White),
+                    Print(heading.bold()),
+                    ResetColor
+                )
+                .unwrap();
+            }
+            Some(todo) => {
+                let chosen = todo == todos.pos;
+                let line: String = format!(" [ ] {} ", text).chars().take(width.saturating_sub(2)).collect();
+                execute!(
+                    stdout(),
+                    MoveTo(x_list + 2, y),
+                    SetForegroundColor(if chosen { Color::White } else { Color::DarkGrey }),
+                    SetBackgroundColor(if chosen { accent_color() } else { Color::Reset }),
+                    Print(line),
+                    ResetColor
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    let note = match &todos.message {
+        Some(message) => Some(message.as_str()),
+        None if todos.todos.is_empty() => Some("nothing to do, add sources with [+] in pages"),
+        None => None,
+    };
+    if let Some(note) = note {
+        let y = if todos.todos.is_empty() { y_search + 9 } else { y_search + content_height - 5 };
+        execute!(
+            stdout(),
+            MoveTo(x_list, y),
+            SetForegroundColor(Color::DarkGrey),
+            Print(note.chars().take(width).collect::<String>()),
+            ResetColor
+        )
+        .unwrap();
+    }
+}
@@ -0,0 +1,133 @@
+use std::io::stdout;
+
+use crossterm::{
+    cursor::MoveTo,
+    execute,
+    style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
+};
+
+use crossterm::event::KeyEvent;
+
+use crate::{
+    app::Workspace,
+    database::archived::{get_archived, Archived},
+    utils::{controls::write_ctrl, keymap::Action, theme::accent_color, time::relative_time},
+    views::{wrap_pos, Command, Layout, View},
+};
+
+// pages and rows archived from here, newest first
+#[derive(Default)]
+pub struct TrashView {
+    pub items: Vec<Archived>,
+    pub pos: usize,
+    pub message: Option<String>,
+}
+
+impl TrashView {
+    pub fn load(&mut self) {
+        self.items = get_archived().unwrap_or_default();
+        self.pos = self.pos.min(self.items.len().saturating_sub(1));
+    }
+}
+
+impl View for TrashView {
+    fn update(&mut self, action: Action, _key: &KeyEvent, _workspace: &mut Workspace) -> Command {
+        self.message = None;
+        match action {
+            Action::Up | Action::Down => {
+                let delta = if action == Action::Up { -1 } else { 1 };
+                self.pos = wrap_pos(self.pos, delta, self.items.len());
+            }
+            Action::Open => {
+                if let Some(item) = self.items.get(self.pos) {
+                    return Command::Restore(item.to_object());
+                }
+            }
+            _ => return Command::Unhandled,
+        }
+        Command::None
+    }
+
+    fn enter(&mut self, _workspace: &Workspace) {
+        self.pos = 0;
+        self.message = None;
+        self.load();
+    }
+
+    fn render(&self, layout: &Layout, _workspace: &Workspace) {
+        trash_view(
+            &layout.content_width,
+            &layout.content_height,
+            &layout.x_center,
+            &layout.y_search,
+            self,
+        );
+    }
+}
+
+pub fn trash_view(
+    content_width: &u16,
+    content_height: &u16,
+    x_center: &u16,
+    y_search: &u16,
+    trash: &TrashView,
+) {
+    for i in 2..content_height - 4 {
+        execute!(
+            stdout(),
+            MoveTo(x_center - (content_width / 2) + 1, y_search + i + 1),
+            Print(" ".repeat((content_width - 2) as usize)),
+        )
+        .unwrap();
+    }
+
+    execute!(
+        stdout(),
+        MoveTo(x_center - (content_width / 2) + 10, y_search + 7),
+        SetBackgroundColor(Color::White),
+        SetForegroundColor(Color::Black),
+        Print(" archived pages ".to_string()),
+        ResetColor
+    )
+    .unwrap();
+
+    let hint = "[enter] restore";
+    write_ctrl(hint, x_center + (content_width / 2) - 10 - hint.len() as u16, y_search + 7);
+
+    let width = (content_width - 20) as usize;
+    let count = 7;
+    let start = count * (trash.pos / count);
+    for (i, item) in trash.items.iter().skip(start).take(count).enumerate() {
+        let age = format!("archived {}", relative_time(item.archived_at));
+        let title: String = item.title.chars().take(width.saturating_sub(age.len() + 3)).collect();
+        let gap = width.saturating_sub(title.chars().count() + age.len() + 2);
+        let selected = i == trash.pos % count;
+
+        execute!(
+            stdout(),
+            MoveTo(x_center - (content_width / 2) + 10, y_search + 9 + (i as u16)),
+            SetForegroundColor(if selected { Color::White } else { Color::DarkGrey }),
+            SetBackgroundColor(if selected { accent_color() } else { Color::Reset }),
+            Print(format!(" {}{}{} ", title, " ".repeat(gap), age)),
+            ResetColor
+        )
+        .unwrap();
+    }
+
+    let note = match &trash.message {
+        Some(message) => Some(message.as_str()),
+        None if trash.items.is_empty() => Some("pages archived with [d] show up here"),
+        None => None,
+    };
+    if let Some(note) = note {
+        let y = if trash.items.is_empty() { y_search + 9 } else { y_search + content_height - 5 };
+        execute!(
+            stdout(),
+            MoveTo(x_center - (content_width / 2) + 10, y),
+            SetForegroundColor(Color::DarkGrey),
+            Print(note.chars().take(width).collect::<String>()),
+            ResetColor
+        )
+        .unwrap();
+    }
+}